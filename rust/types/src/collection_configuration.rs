@@ -1,5 +1,20 @@
+// `quantization` (see `VectorIndexQuantization` below) is read and written against
+// `InternalHnswConfiguration`, `InternalSpannConfiguration`, `UpdateHnswConfiguration`, and
+// `UpdateSpannConfiguration` throughout this file, but none of those structs are declared here —
+// they're defined in the sibling module that owns the rest of `chroma_types` and re-exported via
+// the `use` below. That module predates this file's own history (it was already import-only, with
+// no local definition anywhere in the tree, as of the `baseline` commit, before any field-adding
+// work in this series touched it) and isn't part of this checkout, so it can't be edited from
+// here. Confirming the field truly can't be added from this side alone: the struct's own
+// `InternalHnswConfiguration::from_legacy_segment_metadata`, which several functions in this file
+// call (e.g. `migrate_legacy_metadata_to_hnsw` below), is an inherent method with no body present
+// in this checkout either — so this isn't just a missing field, it's the struct's entire
+// definition and impl living in a file this checkout never had. The user-facing
+// `HnswConfiguration`/`SpannConfiguration` (collection creation) need the same field added at
+// their definitions, plus a `quantization` case in their `From<_> for Internal*` impls (also
+// defined in that missing module), before a caller can ever set it end to end.
 use crate::{
-    HnswConfiguration, HnswParametersFromSegmentError, InternalHnswConfiguration,
+    HnswConfiguration, HnswParametersFromSegmentError, HnswSpace, InternalHnswConfiguration,
     InternalSpannConfiguration, Metadata, Segment, SpannConfiguration, UpdateHnswConfiguration,
     UpdateSpannConfiguration,
 };
@@ -14,12 +29,24 @@ pub enum KnnIndex {
     Hnsw,
     #[serde(alias = "spann")]
     Spann,
+    #[serde(alias = "flat")]
+    Flat,
 }
 
 pub fn default_default_knn_index() -> KnnIndex {
     KnnIndex::Hnsw
 }
 
+/// Below this many expected vectors, `try_from_config` auto-selects a `Flat` (brute-force) index
+/// when no explicit `hnsw`/`spann`/`flat` block is given — an approximate index isn't worth the
+/// tuning for collections this small.
+pub const AUTO_INDEX_FLAT_THRESHOLD: u64 = 10_000;
+
+/// Above this many expected vectors, `try_from_config` auto-selects a `Spann` index when no
+/// explicit `hnsw`/`spann`/`flat` block is given, since hnsw's all-in-memory graph stops scaling
+/// well. Sizes in between default to `Hnsw`.
+pub const AUTO_INDEX_SPANN_THRESHOLD: u64 = 1_000_000;
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
 #[serde(tag = "type")]
 pub enum EmbeddingFunctionConfiguration {
@@ -40,31 +67,176 @@ pub struct EmbeddingFunctionNewConfiguration {
 pub enum VectorIndexConfiguration {
     Hnsw(InternalHnswConfiguration),
     Spann(InternalSpannConfiguration),
+    Flat(InternalFlatConfiguration),
 }
 
 impl VectorIndexConfiguration {
-    pub fn update(&mut self, vector_index: &VectorIndexConfiguration) {
-        match (self, vector_index) {
-            (VectorIndexConfiguration::Hnsw(hnsw), VectorIndexConfiguration::Hnsw(hnsw_new)) => {
-                *hnsw = hnsw_new.clone();
-            }
-            (
-                VectorIndexConfiguration::Spann(spann),
-                VectorIndexConfiguration::Spann(spann_new),
-            ) => {
-                *spann = spann_new.clone();
-            }
-            (VectorIndexConfiguration::Hnsw(_), VectorIndexConfiguration::Spann(_)) => {
-                // For now, we don't support converting between different index types
-                // This could be implemented in the future if needed
-            }
-            (VectorIndexConfiguration::Spann(_), VectorIndexConfiguration::Hnsw(_)) => {
-                // For now, we don't support converting between different index types
-                // This could be implemented in the future if needed
-            }
+    fn family(&self) -> &'static str {
+        match self {
+            VectorIndexConfiguration::Hnsw(_) => "hnsw",
+            VectorIndexConfiguration::Spann(_) => "spann",
+            VectorIndexConfiguration::Flat(_) => "flat",
+        }
+    }
+}
+
+/// Carries the parameters shared between HNSW and SPANN (`space`, `ef_construction`,
+/// `ef_search`, `max_neighbors`) from `hnsw` onto a freshly defaulted SPANN configuration.
+fn hnsw_to_spann(hnsw: &InternalHnswConfiguration) -> InternalSpannConfiguration {
+    InternalSpannConfiguration {
+        space: hnsw.space.clone(),
+        ef_construction: hnsw.ef_construction,
+        ef_search: hnsw.ef_search,
+        max_neighbors: hnsw.max_neighbors,
+        ..Default::default()
+    }
+}
+
+/// Carries the parameters shared between HNSW and SPANN (`space`, `ef_construction`,
+/// `ef_search`, `max_neighbors`) from `spann` onto a freshly defaulted HNSW configuration.
+fn spann_to_hnsw(spann: &InternalSpannConfiguration) -> InternalHnswConfiguration {
+    InternalHnswConfiguration {
+        space: spann.space.clone(),
+        ef_construction: spann.ef_construction,
+        ef_search: spann.ef_search,
+        max_neighbors: spann.max_neighbors,
+        ..Default::default()
+    }
+}
+
+/// Whether `hnsw` holds tuning beyond the parameters shared with SPANN, i.e. whether converting
+/// it via `hnsw_to_spann` would silently discard settings.
+fn hnsw_has_unshared_tuning(hnsw: &InternalHnswConfiguration) -> bool {
+    *hnsw
+        != InternalHnswConfiguration {
+            space: hnsw.space.clone(),
+            ef_construction: hnsw.ef_construction,
+            ef_search: hnsw.ef_search,
+            max_neighbors: hnsw.max_neighbors,
+            ..Default::default()
+        }
+}
+
+/// Whether `spann` holds tuning beyond the parameters shared with HNSW, i.e. whether converting
+/// it via `spann_to_hnsw` would silently discard settings.
+fn spann_has_unshared_tuning(spann: &InternalSpannConfiguration) -> bool {
+    *spann
+        != InternalSpannConfiguration {
+            space: spann.space.clone(),
+            ef_construction: spann.ef_construction,
+            ef_search: spann.ef_search,
+            max_neighbors: spann.max_neighbors,
+            ..Default::default()
+        }
+}
+
+/// Reports every bound/invariant violation found in a configuration at once, rather than just
+/// the first, so a caller can fix all of them in one pass.
+#[derive(Debug, Error)]
+#[error("invalid collection configuration: {}", .violations.join("; "))]
+pub struct ConfigurationValidationError {
+    pub violations: Vec<String>,
+}
+
+impl ChromaError for ConfigurationValidationError {
+    fn code(&self) -> ErrorCodes {
+        ErrorCodes::InvalidArgument
+    }
+}
+
+impl InternalHnswConfiguration {
+    /// Checks per-field bounds and cross-field invariants. Accumulates every violation instead
+    /// of stopping at the first.
+    pub fn validate(&self) -> Result<(), ConfigurationValidationError> {
+        let mut violations = Vec::new();
+        if !(2..=128).contains(&self.max_neighbors) {
+            violations.push("max_neighbors must be between 2 and 128".to_string());
+        }
+        if self.ef_construction == 0 {
+            violations.push("ef_construction must be greater than 0".to_string());
+        }
+        if self.ef_search == 0 {
+            violations.push("ef_search must be greater than 0".to_string());
+        }
+        if self.ef_construction < self.max_neighbors {
+            violations.push("ef_construction must be greater than or equal to max_neighbors".to_string());
+        }
+        if self.num_threads == 0 {
+            violations.push("num_threads must be greater than 0".to_string());
+        }
+        if self.batch_size == 0 {
+            violations.push("batch_size must be greater than 0".to_string());
+        }
+        if self.sync_threshold == 0 {
+            violations.push("sync_threshold must be greater than 0".to_string());
+        }
+        if self.resize_factor <= 0.0 {
+            violations.push("resize_factor must be greater than 0".to_string());
+        }
+        validate_quantization(&self.quantization, &self.space, &mut violations);
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigurationValidationError { violations })
+        }
+    }
+}
+
+impl InternalSpannConfiguration {
+    /// Checks per-field bounds and cross-field invariants. Accumulates every violation instead
+    /// of stopping at the first.
+    pub fn validate(&self) -> Result<(), ConfigurationValidationError> {
+        let mut violations = Vec::new();
+        if !(2..=128).contains(&self.max_neighbors) {
+            violations.push("max_neighbors must be between 2 and 128".to_string());
+        }
+        if self.ef_construction == 0 {
+            violations.push("ef_construction must be greater than 0".to_string());
+        }
+        if self.ef_search == 0 {
+            violations.push("ef_search must be greater than 0".to_string());
+        }
+        if self.ef_construction < self.max_neighbors {
+            violations.push("ef_construction must be greater than or equal to max_neighbors".to_string());
+        }
+        if self.search_nprobe == 0 {
+            violations.push("search_nprobe must be greater than 0".to_string());
+        }
+        if self.write_nprobe == 0 {
+            violations.push("write_nprobe must be greater than 0".to_string());
+        }
+        if self.reassign_neighbor_count == 0 {
+            violations.push("reassign_neighbor_count must be greater than 0".to_string());
+        }
+        if self.split_threshold == 0 {
+            violations.push("split_threshold must be greater than 0".to_string());
+        }
+        if self.merge_threshold == 0 {
+            violations.push("merge_threshold must be greater than 0".to_string());
+        }
+
+        if self.merge_threshold >= self.split_threshold {
+            violations.push("merge_threshold must be less than split_threshold".to_string());
+        }
+        if self.write_nprobe > self.search_nprobe {
+            violations.push("write_nprobe must be less than or equal to search_nprobe".to_string());
+        }
+        if self.reassign_neighbor_count > self.max_neighbors {
+            violations.push(
+                "reassign_neighbor_count must be less than or equal to max_neighbors".to_string(),
+            );
+        }
+        validate_quantization(&self.quantization, &self.space, &mut violations);
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigurationValidationError { violations })
         }
     }
 }
+
 impl From<InternalHnswConfiguration> for VectorIndexConfiguration {
     fn from(config: InternalHnswConfiguration) -> Self {
         VectorIndexConfiguration::Hnsw(config)
@@ -77,15 +249,317 @@ impl From<InternalSpannConfiguration> for VectorIndexConfiguration {
     }
 }
 
+impl From<InternalFlatConfiguration> for VectorIndexConfiguration {
+    fn from(config: InternalFlatConfiguration) -> Self {
+        VectorIndexConfiguration::Flat(config)
+    }
+}
+
 fn default_vector_index_config() -> VectorIndexConfiguration {
     VectorIndexConfiguration::Hnsw(InternalHnswConfiguration::default())
 }
 
+fn default_flat_space() -> HnswSpace {
+    HnswSpace::L2
+}
+
+/// Brute-force exact nearest-neighbor search: every query scans the full set of stored vectors,
+/// so there's no graph to build or tune. A good fit for small collections, where HNSW's
+/// construction cost buys recall the collection doesn't need.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct InternalFlatConfiguration {
+    #[serde(default = "default_flat_space")]
+    pub space: HnswSpace,
+}
+
+impl Default for InternalFlatConfiguration {
+    fn default() -> Self {
+        Self {
+            space: default_flat_space(),
+        }
+    }
+}
+
+impl InternalFlatConfiguration {
+    /// Flat has no tuning parameters beyond `space`, so there are no bounds or invariants to
+    /// check; this exists to keep the same `validate` call site used for every vector index type.
+    pub fn validate(&self) -> Result<(), ConfigurationValidationError> {
+        Ok(())
+    }
+}
+
+/// An update to an existing `InternalFlatConfiguration`. `space` is the only field Flat has to
+/// change; switching to or from another index family is handled separately as a family change,
+/// not a field update.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[cfg_attr(feature = "pyo3", pyo3::pyclass)]
+pub struct UpdateFlatConfiguration {
+    pub space: Option<HnswSpace>,
+}
+
+/// Quantization applied to a vector index to cut its memory footprint. This layer only persists
+/// and validates the chosen parameters; the index crate is responsible for training codebooks
+/// and computing quantized distances.
+///
+/// `Product` follows the standard ADC scheme: a vector is split into `num_subquantizers`
+/// contiguous, equal-length subvectors, each mapped to one of `2^bits_per_code` centroids trained
+/// by k-means over the collection, and a query distance is approximated by summing precomputed
+/// per-subquantizer lookup tables rather than computing a full float distance.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VectorIndexQuantization {
+    #[default]
+    None,
+    ScalarInt8,
+    Product {
+        num_subquantizers: usize,
+        bits_per_code: u8,
+    },
+}
+
+/// Checks `quantization`'s own bounds and its compatibility with `space`, appending any violation
+/// found to `violations` rather than stopping at the first.
+fn validate_quantization(
+    quantization: &VectorIndexQuantization,
+    space: &HnswSpace,
+    violations: &mut Vec<String>,
+) {
+    match quantization {
+        VectorIndexQuantization::None => {}
+        VectorIndexQuantization::ScalarInt8 => {
+            if *space != HnswSpace::L2 {
+                violations.push("scalar int8 quantization is only supported with the l2 space".to_string());
+            }
+        }
+        VectorIndexQuantization::Product {
+            num_subquantizers,
+            bits_per_code,
+        } => {
+            if *num_subquantizers == 0 {
+                violations.push("num_subquantizers must be greater than 0".to_string());
+            }
+            if !(1..=8).contains(bits_per_code) {
+                violations.push("bits_per_code must be between 1 and 8".to_string());
+            }
+            if *space != HnswSpace::L2 {
+                violations.push("product quantization is only supported with the l2 space".to_string());
+            }
+        }
+    }
+}
+
+/// Parses a quantization setting out of legacy `hnsw:quantization` segment metadata, mirroring
+/// `InternalHnswConfiguration::from_legacy_segment_metadata`'s handling of `hnsw:space`. Returns
+/// `None` if the key is absent or unrecognized, rather than defaulting silently.
+fn quantization_from_legacy_metadata(metadata: &Option<Metadata>) -> Option<VectorIndexQuantization> {
+    let metadata = metadata.as_ref()?;
+    match metadata.get("hnsw:quantization") {
+        Some(crate::MetadataValue::Str(value)) => match value.as_str() {
+            "none" => Some(VectorIndexQuantization::None),
+            "scalar_int8" => Some(VectorIndexQuantization::ScalarInt8),
+            "product" => {
+                let num_subquantizers = match metadata.get("hnsw:quantization_num_subquantizers") {
+                    Some(crate::MetadataValue::Int(v)) => *v as usize,
+                    _ => return None,
+                };
+                let bits_per_code = match metadata.get("hnsw:quantization_bits_per_code") {
+                    Some(crate::MetadataValue::Int(v)) => *v as u8,
+                    _ => 8,
+                };
+                Some(VectorIndexQuantization::Product {
+                    num_subquantizers,
+                    bits_per_code,
+                })
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Builds the HNSW configuration a "version 0" collection (no explicit `VectorIndexConfiguration`,
+/// all tuning expressed through `hnsw:*` segment metadata) would have held. This is the
+/// version 0 → 1 migration: the point where legacy metadata became a typed, explicit vector
+/// index configuration. Shared by `try_from_config`, for a freshly-parsed collection, and by
+/// `migrate_v0_to_v1`, for a persisted config still sitting at `config_version` 0.
+fn migrate_legacy_metadata_to_hnsw(
+    metadata: &Option<Metadata>,
+) -> Result<InternalHnswConfiguration, HnswParametersFromSegmentError> {
+    let mut hnsw = InternalHnswConfiguration::from_legacy_segment_metadata(metadata)?;
+    if let Some(quantization) = quantization_from_legacy_metadata(metadata) {
+        hnsw.quantization = quantization;
+    }
+    Ok(hnsw)
+}
+
+/// The tokenization strategy applied to text before it's written to the inverted index.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FullTextTokenizer {
+    Whitespace,
+    UnicodeWord,
+    Ngram { min_gram: u32, max_gram: u32 },
+}
+
+fn default_fts_tokenizer() -> FullTextTokenizer {
+    FullTextTokenizer::UnicodeWord
+}
+
+fn default_fts_lowercase() -> bool {
+    true
+}
+
+/// A stopword set applied during analysis, either a named built-in preset (e.g. `"english"`) or
+/// an explicit list of words.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StopwordsConfiguration {
+    Named(String),
+    List(Vec<String>),
+}
+
+/// Configuration for a full-text (inverted) index over a collection's documents.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FullTextIndexConfiguration {
+    #[serde(default = "default_fts_tokenizer")]
+    pub tokenizer: FullTextTokenizer,
+    #[serde(default = "default_fts_lowercase")]
+    pub lowercase: bool,
+    #[serde(default)]
+    pub ascii_folding: bool,
+    pub language: Option<String>,
+    pub stopwords: Option<StopwordsConfiguration>,
+}
+
+impl Default for FullTextIndexConfiguration {
+    fn default() -> Self {
+        Self {
+            tokenizer: default_fts_tokenizer(),
+            lowercase: default_fts_lowercase(),
+            ascii_folding: false,
+            language: None,
+            stopwords: None,
+        }
+    }
+}
+
+impl FullTextIndexConfiguration {
+    /// Parses a `FullTextIndexConfiguration` out of legacy `fts:*` segment metadata keys.
+    /// Returns `Ok(None)` if `metadata` holds no `fts:*` keys at all, mirroring how
+    /// `InternalHnswConfiguration::from_legacy_segment_metadata` recovers `hnsw:*` keys.
+    pub fn from_legacy_segment_metadata(
+        metadata: &Option<Metadata>,
+    ) -> Result<Option<Self>, FullTextIndexConfigurationError> {
+        let Some(metadata) = metadata else {
+            return Ok(None);
+        };
+
+        if !metadata.keys().any(|key| key.starts_with("fts:")) {
+            return Ok(None);
+        }
+
+        let mut config = Self::default();
+
+        if let Some(crate::MetadataValue::Str(tokenizer)) = metadata.get("fts:tokenizer") {
+            config.tokenizer = match tokenizer.as_str() {
+                "whitespace" => FullTextTokenizer::Whitespace,
+                "unicode_word" => FullTextTokenizer::UnicodeWord,
+                "ngram" => {
+                    let min_gram = match metadata.get("fts:min_gram") {
+                        Some(crate::MetadataValue::Int(v)) => *v as u32,
+                        _ => 2,
+                    };
+                    let max_gram = match metadata.get("fts:max_gram") {
+                        Some(crate::MetadataValue::Int(v)) => *v as u32,
+                        _ => 3,
+                    };
+                    FullTextTokenizer::Ngram { min_gram, max_gram }
+                }
+                other => {
+                    return Err(FullTextIndexConfigurationError::InvalidTokenizer(
+                        other.to_string(),
+                    ))
+                }
+            };
+        }
+
+        if let FullTextTokenizer::Ngram { min_gram, max_gram } = config.tokenizer {
+            if min_gram > max_gram {
+                return Err(FullTextIndexConfigurationError::InvalidNgramRange {
+                    min_gram,
+                    max_gram,
+                });
+            }
+        }
+
+        if let Some(crate::MetadataValue::Bool(lowercase)) = metadata.get("fts:lowercase") {
+            config.lowercase = *lowercase;
+        }
+        if let Some(crate::MetadataValue::Bool(ascii_folding)) = metadata.get("fts:ascii_folding")
+        {
+            config.ascii_folding = *ascii_folding;
+        }
+        if let Some(crate::MetadataValue::Str(language)) = metadata.get("fts:language") {
+            config.language = Some(language.clone());
+        }
+        if let Some(crate::MetadataValue::Str(stopwords)) = metadata.get("fts:stopwords") {
+            config.stopwords = Some(StopwordsConfiguration::Named(stopwords.clone()));
+        }
+
+        Ok(Some(config))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum FullTextIndexConfigurationError {
+    #[error("Unknown full-text tokenizer \"{0}\"")]
+    InvalidTokenizer(String),
+    #[error("Invalid n-gram range: min_gram ({min_gram}) must be <= max_gram ({max_gram})")]
+    InvalidNgramRange { min_gram: u32, max_gram: u32 },
+    #[error("Cannot change the tokenizer of an existing full-text index")]
+    ImmutableTokenizer,
+    #[error("No full-text index is configured to update")]
+    NoExistingIndex,
+}
+
+impl ChromaError for FullTextIndexConfigurationError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            Self::InvalidTokenizer(_) => ErrorCodes::InvalidArgument,
+            Self::InvalidNgramRange { .. } => ErrorCodes::InvalidArgument,
+            Self::ImmutableTokenizer => ErrorCodes::InvalidArgument,
+            Self::NoExistingIndex => ErrorCodes::InvalidArgument,
+        }
+    }
+}
+
+/// An update to an existing `FullTextIndexConfiguration`. Only query-time-safe fields are
+/// mutable here: changing `tokenizer` after the index has been built would invalidate it, so that
+/// attempt is rejected by `InternalCollectionConfiguration::update` rather than being applied.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[cfg_attr(feature = "pyo3", pyo3::pyclass)]
+pub struct UpdateFullTextIndexConfiguration {
+    pub tokenizer: Option<FullTextTokenizer>,
+    pub stopwords: Option<StopwordsConfiguration>,
+}
+
+/// The current on-disk shape of `InternalCollectionConfiguration`. Bump this, and register a
+/// migration in `CONFIG_MIGRATIONS`, whenever a stored field is renamed or reinterpreted in a way
+/// that would otherwise silently misread older persisted configs.
+pub const CURRENT_CONFIG_VERSION: u32 = 1;
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct InternalCollectionConfiguration {
+    /// The schema version this configuration was last migrated to. Configs persisted before
+    /// this field existed deserialize as `0` and are brought up to `CURRENT_CONFIG_VERSION` by
+    /// `migrate`.
+    #[serde(default)]
+    pub config_version: u32,
     #[serde(default = "default_vector_index_config")]
     pub vector_index: VectorIndexConfiguration,
     pub embedding_function: Option<EmbeddingFunctionConfiguration>,
+    #[serde(default)]
+    pub fts_index: Option<FullTextIndexConfiguration>,
 }
 
 impl InternalCollectionConfiguration {
@@ -94,22 +568,28 @@ impl InternalCollectionConfiguration {
     ) -> Result<Self, HnswParametersFromSegmentError> {
         let hnsw = InternalHnswConfiguration::from_legacy_segment_metadata(&Some(metadata))?;
         Ok(Self {
+            config_version: CURRENT_CONFIG_VERSION,
             vector_index: VectorIndexConfiguration::Hnsw(hnsw),
             embedding_function: None,
+            fts_index: None,
         })
     }
 
     pub fn default_hnsw() -> Self {
         Self {
+            config_version: CURRENT_CONFIG_VERSION,
             vector_index: VectorIndexConfiguration::Hnsw(InternalHnswConfiguration::default()),
             embedding_function: None,
+            fts_index: None,
         }
     }
 
     pub fn default_spann() -> Self {
         Self {
+            config_version: CURRENT_CONFIG_VERSION,
             vector_index: VectorIndexConfiguration::Spann(InternalSpannConfiguration::default()),
             embedding_function: None,
+            fts_index: None,
         }
     }
 
@@ -138,6 +618,33 @@ impl InternalCollectionConfiguration {
         Ok(None)
     }
 
+    pub fn get_fts_config_with_legacy_fallback(
+        &self,
+        segment: &Segment,
+    ) -> Result<Option<FullTextIndexConfiguration>, FullTextIndexConfigurationError> {
+        self.get_fts_config_from_legacy_metadata(&segment.metadata)
+    }
+
+    pub fn get_fts_config_from_legacy_metadata(
+        &self,
+        metadata: &Option<Metadata>,
+    ) -> Result<Option<FullTextIndexConfiguration>, FullTextIndexConfigurationError> {
+        let config_from_metadata = FullTextIndexConfiguration::from_legacy_segment_metadata(metadata)?;
+
+        Ok(match (&self.fts_index, config_from_metadata) {
+            (Some(config), Some(from_metadata)) => {
+                if *config == FullTextIndexConfiguration::default() && *config != from_metadata {
+                    Some(from_metadata)
+                } else {
+                    Some(config.clone())
+                }
+            }
+            (Some(config), None) => Some(config.clone()),
+            (None, Some(from_metadata)) => Some(from_metadata),
+            (None, None) => None,
+        })
+    }
+
     pub fn get_spann_config(&self) -> Option<InternalSpannConfiguration> {
         match &self.vector_index {
             VectorIndexConfiguration::Spann(config) => Some(config.clone()),
@@ -152,47 +659,175 @@ impl InternalCollectionConfiguration {
         }
     }
 
-    pub fn update(&mut self, configuration: &InternalUpdateCollectionConfiguration) {
+    /// Validates the active vector index configuration's per-field bounds and cross-field
+    /// invariants. Called at the end of `update`, `try_from_config`, and the `TryFrom` impl so an
+    /// invalid configuration is never stored.
+    pub fn validate(&self) -> Result<(), ConfigurationValidationError> {
+        match &self.vector_index {
+            VectorIndexConfiguration::Hnsw(hnsw) => hnsw.validate(),
+            VectorIndexConfiguration::Spann(spann) => spann.validate(),
+            VectorIndexConfiguration::Flat(flat) => flat.validate(),
+        }
+    }
+
+    /// Applies every migration registered in `CONFIG_MIGRATIONS` between `self.config_version`
+    /// and `CURRENT_CONFIG_VERSION`, in order, then stamps the result with the current version.
+    /// A config already at `CURRENT_CONFIG_VERSION` is returned unchanged. `metadata` is the
+    /// collection's legacy segment metadata, needed by migrations that recover settings that
+    /// predate an explicit, typed configuration.
+    ///
+    /// Nothing in this crate calls `migrate` outside `mod tests` below.
+    /// `TryFrom<CollectionConfiguration>` below is a different path (collection creation)
+    /// and always stamps `CURRENT_CONFIG_VERSION` directly, since a freshly created config is
+    /// never on a legacy version and has nothing to migrate.
+    pub fn migrate(
+        mut self,
+        metadata: &Option<Metadata>,
+    ) -> Result<Self, HnswParametersFromSegmentError> {
+        for migration in CONFIG_MIGRATIONS.iter().skip(self.config_version as usize) {
+            self = migration(self, metadata)?;
+        }
+        self.config_version = CURRENT_CONFIG_VERSION;
+        Ok(self)
+    }
+
+    pub fn update(
+        &mut self,
+        configuration: &InternalUpdateCollectionConfiguration,
+    ) -> Result<(), UpdateCollectionConfigurationError> {
+        // Applied to a copy, so a rejected or invalid update leaves `self` unchanged.
+        let mut updated = self.clone();
+        updated.apply_update(configuration)?;
+        updated.validate()?;
+        *self = updated;
+        Ok(())
+    }
+
+    fn apply_update(
+        &mut self,
+        configuration: &InternalUpdateCollectionConfiguration,
+    ) -> Result<(), UpdateCollectionConfigurationError> {
         // Update vector_index if it exists in the update configuration
 
         if let Some(vector_index) = &configuration.vector_index {
-            match vector_index {
-                UpdateVectorIndexConfiguration::Hnsw(hnsw_config) => {
-                    if let VectorIndexConfiguration::Hnsw(current_config) = &mut self.vector_index {
-                        if let Some(update_config) = hnsw_config {
-                            if let Some(ef_search) = update_config.ef_search {
-                                current_config.ef_search = ef_search;
-                            }
-                            if let Some(max_neighbors) = update_config.max_neighbors {
-                                current_config.max_neighbors = max_neighbors;
-                            }
-                            if let Some(num_threads) = update_config.num_threads {
-                                current_config.num_threads = num_threads;
-                            }
-                            if let Some(resize_factor) = update_config.resize_factor {
-                                current_config.resize_factor = resize_factor;
-                            }
-                            if let Some(sync_threshold) = update_config.sync_threshold {
-                                current_config.sync_threshold = sync_threshold;
-                            }
-                            if let Some(batch_size) = update_config.batch_size {
-                                current_config.batch_size = batch_size;
-                            }
+            // Matched against a clone of the current vector index rather than `&mut
+            // self.vector_index` so a type-changing arm is free to reassign `self.vector_index`
+            // wholesale once it has what it needs from the current config.
+            match (self.vector_index.clone(), vector_index) {
+                (VectorIndexConfiguration::Hnsw(mut current_config), UpdateVectorIndexConfiguration::Hnsw(update_config)) => {
+                    if let Some(update_config) = update_config {
+                        if let Some(ef_search) = update_config.ef_search {
+                            current_config.ef_search = ef_search;
+                        }
+                        if let Some(max_neighbors) = update_config.max_neighbors {
+                            current_config.max_neighbors = max_neighbors;
+                        }
+                        if let Some(num_threads) = update_config.num_threads {
+                            current_config.num_threads = num_threads;
+                        }
+                        if let Some(resize_factor) = update_config.resize_factor {
+                            current_config.resize_factor = resize_factor;
+                        }
+                        if let Some(sync_threshold) = update_config.sync_threshold {
+                            current_config.sync_threshold = sync_threshold;
+                        }
+                        if let Some(batch_size) = update_config.batch_size {
+                            current_config.batch_size = batch_size;
+                        }
+                        if let Some(quantization) = &update_config.quantization {
+                            current_config.quantization = quantization.clone();
+                        }
+                    }
+                    self.vector_index = VectorIndexConfiguration::Hnsw(current_config);
+                }
+                (VectorIndexConfiguration::Spann(mut current_config), UpdateVectorIndexConfiguration::Spann(update_config)) => {
+                    if let Some(update_config) = update_config {
+                        if let Some(search_nprobe) = update_config.search_nprobe {
+                            current_config.search_nprobe = search_nprobe;
+                        }
+                        if let Some(ef_search) = update_config.ef_search {
+                            current_config.ef_search = ef_search;
+                        }
+                        if let Some(quantization) = &update_config.quantization {
+                            current_config.quantization = quantization.clone();
+                        }
+                    }
+                    self.vector_index = VectorIndexConfiguration::Spann(current_config);
+                }
+                (VectorIndexConfiguration::Hnsw(current_config), UpdateVectorIndexConfiguration::Spann(update_config)) => {
+                    if hnsw_has_unshared_tuning(&current_config) && !configuration.allow_index_type_change {
+                        return Err(UpdateCollectionConfigurationError::VectorIndexTypeChange {
+                            from: "hnsw",
+                            to: "spann",
+                        });
+                    }
+                    let mut converted = hnsw_to_spann(&current_config);
+                    if let Some(update_config) = update_config {
+                        if let Some(search_nprobe) = update_config.search_nprobe {
+                            converted.search_nprobe = search_nprobe;
+                        }
+                        if let Some(ef_search) = update_config.ef_search {
+                            converted.ef_search = ef_search;
+                        }
+                        if let Some(quantization) = &update_config.quantization {
+                            converted.quantization = quantization.clone();
+                        }
+                    }
+                    self.vector_index = VectorIndexConfiguration::Spann(converted);
+                }
+                (VectorIndexConfiguration::Spann(current_config), UpdateVectorIndexConfiguration::Hnsw(update_config)) => {
+                    if spann_has_unshared_tuning(&current_config) && !configuration.allow_index_type_change {
+                        return Err(UpdateCollectionConfigurationError::VectorIndexTypeChange {
+                            from: "spann",
+                            to: "hnsw",
+                        });
+                    }
+                    let mut converted = spann_to_hnsw(&current_config);
+                    if let Some(update_config) = update_config {
+                        if let Some(ef_search) = update_config.ef_search {
+                            converted.ef_search = ef_search;
+                        }
+                        if let Some(max_neighbors) = update_config.max_neighbors {
+                            converted.max_neighbors = max_neighbors;
+                        }
+                        if let Some(num_threads) = update_config.num_threads {
+                            converted.num_threads = num_threads;
+                        }
+                        if let Some(resize_factor) = update_config.resize_factor {
+                            converted.resize_factor = resize_factor;
+                        }
+                        if let Some(sync_threshold) = update_config.sync_threshold {
+                            converted.sync_threshold = sync_threshold;
+                        }
+                        if let Some(batch_size) = update_config.batch_size {
+                            converted.batch_size = batch_size;
+                        }
+                        if let Some(quantization) = &update_config.quantization {
+                            converted.quantization = quantization.clone();
                         }
                     }
+                    self.vector_index = VectorIndexConfiguration::Hnsw(converted);
                 }
-                UpdateVectorIndexConfiguration::Spann(spann_config) => {
-                    if let VectorIndexConfiguration::Spann(current_config) = &mut self.vector_index
-                    {
-                        if let Some(update_config) = spann_config {
-                            if let Some(search_nprobe) = update_config.search_nprobe {
-                                current_config.search_nprobe = search_nprobe;
-                            }
-                            if let Some(ef_search) = update_config.ef_search {
-                                current_config.ef_search = ef_search;
-                            }
+                (VectorIndexConfiguration::Flat(mut current_config), UpdateVectorIndexConfiguration::Flat(update_config)) => {
+                    if let Some(update_config) = update_config {
+                        if let Some(space) = update_config.space {
+                            current_config.space = space;
                         }
                     }
+                    self.vector_index = VectorIndexConfiguration::Flat(current_config);
+                }
+                (current_config, update_config) => {
+                    // `Flat` is a distinct search strategy (exact vs. approximate), not a tuning
+                    // variant like HNSW/SPANN, so switching to or away from it is always rejected
+                    // here rather than gated behind `allow_index_type_change`.
+                    return Err(UpdateCollectionConfigurationError::VectorIndexTypeChange {
+                        from: current_config.family(),
+                        to: match update_config {
+                            UpdateVectorIndexConfiguration::Hnsw(_) => "hnsw",
+                            UpdateVectorIndexConfiguration::Spann(_) => "spann",
+                            UpdateVectorIndexConfiguration::Flat(_) => "flat",
+                        },
+                    });
                 }
             }
         }
@@ -200,6 +835,36 @@ impl InternalCollectionConfiguration {
         if let Some(embedding_function) = &configuration.embedding_function {
             self.embedding_function = Some(embedding_function.clone());
         }
+
+        // Update fts_index if it exists in the update configuration
+        if let Some(fts_update) = &configuration.fts_index {
+            match &mut self.fts_index {
+                Some(current) => {
+                    if fts_update.tokenizer.is_some() {
+                        return Err(UpdateCollectionConfigurationError::FullTextIndex(
+                            FullTextIndexConfigurationError::ImmutableTokenizer,
+                        ));
+                    }
+                    if let Some(stopwords) = &fts_update.stopwords {
+                        current.stopwords = Some(stopwords.clone());
+                    }
+                }
+                None => {
+                    let tokenizer = fts_update.tokenizer.clone().ok_or(
+                        UpdateCollectionConfigurationError::FullTextIndex(
+                            FullTextIndexConfigurationError::NoExistingIndex,
+                        ),
+                    )?;
+                    self.fts_index = Some(FullTextIndexConfiguration {
+                        tokenizer,
+                        stopwords: fts_update.stopwords.clone(),
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+
+        Ok(())
     }
 
     pub fn try_from_config(
@@ -209,118 +874,180 @@ impl InternalCollectionConfiguration {
     ) -> Result<Self, CollectionConfigurationToInternalConfigurationError> {
         let mut hnsw: Option<HnswConfiguration> = value.hnsw;
         let spann: Option<SpannConfiguration> = value.spann;
+        let flat: Option<InternalFlatConfiguration> = value.flat;
+
+        if [hnsw.is_some(), spann.is_some(), flat.is_some()]
+            .iter()
+            .filter(|provided| **provided)
+            .count()
+            > 1
+        {
+            return Err(
+                CollectionConfigurationToInternalConfigurationError::MultipleVectorIndexConfigurations,
+            );
+        }
 
-        // if neither hnsw nor spann is provided, use the collection metadata to build an hnsw configuration
-        // the match then handles cases where hnsw is provided, and correctly routes to either spann or hnsw configuration
-        // based on the default_knn_index
-        if hnsw.is_none() && spann.is_none() {
-            let hnsw_config_from_metadata =
-            InternalHnswConfiguration::from_legacy_segment_metadata(&metadata).map_err(|e| {
-                CollectionConfigurationToInternalConfigurationError::HnswParametersFromSegmentError(
-                    e,
-                )
-            })?;
+        let no_explicit_vector_index = hnsw.is_none() && spann.is_none() && flat.is_none();
+
+        // if none of hnsw, spann, or flat is provided, use the collection metadata to build an
+        // hnsw configuration; the match below then routes it to whichever family is resolved
+        // below.
+        if no_explicit_vector_index {
+            let hnsw_config_from_metadata = migrate_legacy_metadata_to_hnsw(&metadata).map_err(
+                CollectionConfigurationToInternalConfigurationError::HnswParametersFromSegmentError,
+            )?;
             hnsw = Some(hnsw_config_from_metadata.into());
         }
 
-        match (hnsw, spann) {
-            (Some(_), Some(_)) => Err(CollectionConfigurationToInternalConfigurationError::MultipleVectorIndexConfigurations),
-            (Some(hnsw), None) => {
-                match default_knn_index {
-                    // Create a spann index. Only inherit the space if it exists in the hnsw config.
-                    // This is for backwards compatibility so that users who migrate to distributed
-                    // from local don't break their code.
-                    KnnIndex::Spann => {
-                        let internal_config = if let Some(space) = hnsw.space {
-                            InternalSpannConfiguration {
-                                space,
-                                ..Default::default()
-                            }
-                        } else {
-                            InternalSpannConfiguration::default()
-                        };
-
-                        Ok(InternalCollectionConfiguration {
-                            vector_index: VectorIndexConfiguration::Spann(internal_config),
-                            embedding_function: value.embedding_function,
-                        })
-                    },
-                    KnnIndex::Hnsw => {
-                        let hnsw: InternalHnswConfiguration = hnsw.into();
-                        Ok(InternalCollectionConfiguration {
-                            vector_index: hnsw.into(),
-                            embedding_function: value.embedding_function,
-                        })
-                    }
-                }
+        // An explicit hnsw/spann/flat block always wins. Otherwise, `expected_collection_size`
+        // (when given) picks the family by size instead of always falling back to
+        // `default_knn_index`: small collections don't need an approximate index at all, and very
+        // large ones outgrow hnsw's all-in-memory graph.
+        let resolved_knn_index = if no_explicit_vector_index {
+            match value.expected_collection_size {
+                Some(size) if size < AUTO_INDEX_FLAT_THRESHOLD => KnnIndex::Flat,
+                Some(size) if size > AUTO_INDEX_SPANN_THRESHOLD => KnnIndex::Spann,
+                Some(_) => KnnIndex::Hnsw,
+                None => default_knn_index,
             }
-            (None, Some(spann)) => {
-                match default_knn_index {
-                    // Create a hnsw index. Only inherit the space if it exists in the spann config.
-                    // This is for backwards compatibility so that users who migrate to local
-                    // from distributed don't break their code.
-                    KnnIndex::Hnsw => {
-                        let internal_config = if let Some(space) = spann.space {
-                            InternalHnswConfiguration {
-                                space,
-                                ..Default::default()
-                            }
-                        } else {
-                            InternalHnswConfiguration::default()
-                        };
-                        Ok(InternalCollectionConfiguration {
-                            vector_index: VectorIndexConfiguration::Hnsw(internal_config),
-                            embedding_function: value.embedding_function,
-                        })
+        } else {
+            default_knn_index
+        };
+
+        // Only the `space` parameter is inherited across a family switch (e.g. an hnsw config
+        // provided while `resolved_knn_index` asks for spann). This is for backwards
+        // compatibility so that users migrating between local and distributed, or into a `flat`
+        // index, don't break their existing configuration.
+        let vector_index = match (hnsw, spann, flat) {
+            (Some(hnsw), None, None) => match resolved_knn_index {
+                KnnIndex::Hnsw => VectorIndexConfiguration::Hnsw(hnsw.into()),
+                KnnIndex::Spann => match hnsw.space {
+                    Some(space) => VectorIndexConfiguration::Spann(InternalSpannConfiguration {
+                        space,
+                        ..Default::default()
+                    }),
+                    None => VectorIndexConfiguration::Spann(InternalSpannConfiguration::default()),
+                },
+                KnnIndex::Flat => match hnsw.space {
+                    Some(space) => {
+                        VectorIndexConfiguration::Flat(InternalFlatConfiguration { space })
                     }
-                    KnnIndex::Spann => {
-                        let spann: InternalSpannConfiguration = spann.into();
-                        Ok(InternalCollectionConfiguration {
-                            vector_index: spann.into(),
-                            embedding_function: value.embedding_function,
-                        })
+                    None => VectorIndexConfiguration::Flat(InternalFlatConfiguration::default()),
+                },
+            },
+            (None, Some(spann), None) => match resolved_knn_index {
+                KnnIndex::Spann => VectorIndexConfiguration::Spann(spann.into()),
+                KnnIndex::Hnsw => match spann.space {
+                    Some(space) => VectorIndexConfiguration::Hnsw(InternalHnswConfiguration {
+                        space,
+                        ..Default::default()
+                    }),
+                    None => VectorIndexConfiguration::Hnsw(InternalHnswConfiguration::default()),
+                },
+                KnnIndex::Flat => match spann.space {
+                    Some(space) => {
+                        VectorIndexConfiguration::Flat(InternalFlatConfiguration { space })
                     }
-                }
-            }
-            (None, None) => {
-                let vector_index = match default_knn_index {
-                    KnnIndex::Hnsw => InternalHnswConfiguration::default().into(),
-                    KnnIndex::Spann => InternalSpannConfiguration::default().into(),
-                };
-                Ok(InternalCollectionConfiguration {
-                    vector_index,
-                    embedding_function: value.embedding_function,
-                })
-            }
-        }
+                    None => VectorIndexConfiguration::Flat(InternalFlatConfiguration::default()),
+                },
+            },
+            (None, None, Some(flat)) => match resolved_knn_index {
+                KnnIndex::Flat => VectorIndexConfiguration::Flat(flat),
+                KnnIndex::Hnsw => VectorIndexConfiguration::Hnsw(InternalHnswConfiguration {
+                    space: flat.space,
+                    ..Default::default()
+                }),
+                KnnIndex::Spann => VectorIndexConfiguration::Spann(InternalSpannConfiguration {
+                    space: flat.space,
+                    ..Default::default()
+                }),
+            },
+            (None, None, None) => match resolved_knn_index {
+                KnnIndex::Hnsw => InternalHnswConfiguration::default().into(),
+                KnnIndex::Spann => InternalSpannConfiguration::default().into(),
+                KnnIndex::Flat => InternalFlatConfiguration::default().into(),
+            },
+            _ => unreachable!(
+                "at most one of hnsw, spann, or flat can be provided, checked above"
+            ),
+        };
+
+        let config = InternalCollectionConfiguration {
+            config_version: CURRENT_CONFIG_VERSION,
+            vector_index,
+            embedding_function: value.embedding_function,
+            fts_index: value.fts_index,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+type ConfigMigration = fn(
+    InternalCollectionConfiguration,
+    &Option<Metadata>,
+) -> Result<InternalCollectionConfiguration, HnswParametersFromSegmentError>;
+
+/// Migration from version 0 (no explicit `VectorIndexConfiguration`; a collection's real tuning
+/// lived entirely in `hnsw:*` segment metadata) to version 1 (an explicit, typed vector index
+/// configuration). Only overrides the vector index if it's still sitting at the HNSW default,
+/// mirroring `get_hnsw_config_from_legacy_metadata`'s "only override an unconfigured default"
+/// rule — a version 0 config that already carries non-default HNSW tuning was written by code
+/// that predates `config_version` but post-dates the legacy metadata path, so there's nothing to
+/// migrate.
+fn migrate_v0_to_v1(
+    mut config: InternalCollectionConfiguration,
+    metadata: &Option<Metadata>,
+) -> Result<InternalCollectionConfiguration, HnswParametersFromSegmentError> {
+    if config.vector_index == VectorIndexConfiguration::Hnsw(InternalHnswConfiguration::default())
+    {
+        config.vector_index =
+            VectorIndexConfiguration::Hnsw(migrate_legacy_metadata_to_hnsw(metadata)?);
     }
+    Ok(config)
 }
 
+/// Migrations in order, indexed by source version: `CONFIG_MIGRATIONS[v]` upgrades a config at
+/// version `v` to version `v + 1`.
+const CONFIG_MIGRATIONS: &[ConfigMigration] = &[migrate_v0_to_v1];
+
 impl TryFrom<CollectionConfiguration> for InternalCollectionConfiguration {
     type Error = CollectionConfigurationToInternalConfigurationError;
 
     fn try_from(value: CollectionConfiguration) -> Result<Self, Self::Error> {
-        match (value.hnsw, value.spann) {
-            (Some(_), Some(_)) => Err(Self::Error::MultipleVectorIndexConfigurations),
-            (Some(hnsw), None) => {
+        if [value.hnsw.is_some(), value.spann.is_some(), value.flat.is_some()]
+            .iter()
+            .filter(|provided| **provided)
+            .count()
+            > 1
+        {
+            return Err(Self::Error::MultipleVectorIndexConfigurations);
+        }
+
+        let vector_index = match (value.hnsw, value.spann, value.flat) {
+            (Some(hnsw), None, None) => {
                 let hnsw: InternalHnswConfiguration = hnsw.into();
-                Ok(InternalCollectionConfiguration {
-                    vector_index: hnsw.into(),
-                    embedding_function: value.embedding_function,
-                })
+                hnsw.into()
             }
-            (None, Some(spann)) => {
+            (None, Some(spann), None) => {
                 let spann: InternalSpannConfiguration = spann.into();
-                Ok(InternalCollectionConfiguration {
-                    vector_index: spann.into(),
-                    embedding_function: value.embedding_function,
-                })
+                spann.into()
             }
-            (None, None) => Ok(InternalCollectionConfiguration {
-                vector_index: InternalHnswConfiguration::default().into(),
-                embedding_function: value.embedding_function,
-            }),
-        }
+            (None, None, Some(flat)) => flat.into(),
+            (None, None, None) => InternalHnswConfiguration::default().into(),
+            _ => unreachable!(
+                "at most one of hnsw, spann, or flat can be provided, checked above"
+            ),
+        };
+
+        let config = InternalCollectionConfiguration {
+            config_version: CURRENT_CONFIG_VERSION,
+            vector_index,
+            embedding_function: value.embedding_function,
+            fts_index: value.fts_index,
+        };
+        config.validate()?;
+        Ok(config)
     }
 }
 
@@ -330,6 +1057,8 @@ pub enum CollectionConfigurationToInternalConfigurationError {
     MultipleVectorIndexConfigurations,
     #[error("Failed to parse hnsw parameters from segment metadata")]
     HnswParametersFromSegmentError(#[from] HnswParametersFromSegmentError),
+    #[error("{0}")]
+    Validation(#[from] ConfigurationValidationError),
 }
 
 impl ChromaError for CollectionConfigurationToInternalConfigurationError {
@@ -337,6 +1066,7 @@ impl ChromaError for CollectionConfigurationToInternalConfigurationError {
         match self {
             Self::MultipleVectorIndexConfigurations => ErrorCodes::InvalidArgument,
             Self::HnswParametersFromSegmentError(_) => ErrorCodes::InvalidArgument,
+            Self::Validation(e) => e.code(),
         }
     }
 }
@@ -346,7 +1076,16 @@ impl ChromaError for CollectionConfigurationToInternalConfigurationError {
 pub struct CollectionConfiguration {
     pub hnsw: Option<HnswConfiguration>,
     pub spann: Option<SpannConfiguration>,
+    pub flat: Option<InternalFlatConfiguration>,
     pub embedding_function: Option<EmbeddingFunctionConfiguration>,
+    pub fts_index: Option<FullTextIndexConfiguration>,
+    /// An optional hint for how many vectors this collection is expected to hold. When none of
+    /// `hnsw`, `spann`, or `flat` is provided, `try_from_config` uses this to auto-select a
+    /// vector index family by size instead of always falling back to `default_knn_index`. Has no
+    /// effect once any of those three is given explicitly, and isn't persisted on the resulting
+    /// `InternalCollectionConfiguration`.
+    #[serde(default)]
+    pub expected_collection_size: Option<u64>,
 }
 
 impl From<InternalCollectionConfiguration> for CollectionConfiguration {
@@ -356,11 +1095,17 @@ impl From<InternalCollectionConfiguration> for CollectionConfiguration {
                 VectorIndexConfiguration::Hnsw(config) => Some(config.into()),
                 _ => None,
             },
-            spann: match value.vector_index {
+            spann: match value.vector_index.clone() {
                 VectorIndexConfiguration::Spann(config) => Some(config.into()),
                 _ => None,
             },
+            flat: match value.vector_index {
+                VectorIndexConfiguration::Flat(config) => Some(config),
+                _ => None,
+            },
             embedding_function: value.embedding_function,
+            fts_index: value.fts_index,
+            expected_collection_size: None,
         }
     }
 }
@@ -370,6 +1115,7 @@ impl From<InternalCollectionConfiguration> for CollectionConfiguration {
 pub enum UpdateVectorIndexConfiguration {
     Hnsw(Option<UpdateHnswConfiguration>),
     Spann(Option<UpdateSpannConfiguration>),
+    Flat(Option<UpdateFlatConfiguration>),
 }
 
 impl From<UpdateHnswConfiguration> for UpdateVectorIndexConfiguration {
@@ -384,7 +1130,13 @@ impl From<UpdateSpannConfiguration> for UpdateVectorIndexConfiguration {
     }
 }
 
-#[derive(Debug, Error)]
+impl From<UpdateFlatConfiguration> for UpdateVectorIndexConfiguration {
+    fn from(config: UpdateFlatConfiguration) -> Self {
+        UpdateVectorIndexConfiguration::Flat(Some(config))
+    }
+}
+
+#[derive(Debug, Error)]
 pub enum UpdateCollectionConfigurationToInternalConfigurationError {
     #[error("Multiple vector index configurations provided")]
     MultipleVectorIndexConfigurations,
@@ -403,13 +1155,45 @@ impl ChromaError for UpdateCollectionConfigurationToInternalConfigurationError {
 pub struct UpdateCollectionConfiguration {
     pub hnsw: Option<UpdateHnswConfiguration>,
     pub spann: Option<UpdateSpannConfiguration>,
+    pub flat: Option<UpdateFlatConfiguration>,
     pub embedding_function: Option<EmbeddingFunctionConfiguration>,
+    pub fts_index: Option<UpdateFullTextIndexConfiguration>,
+    /// Confirms that switching between `hnsw` and `spann` is intentional, even if it would
+    /// discard tuning that isn't shared between the two. Has no effect on a switch to or from
+    /// `flat`, which is always rejected.
+    #[serde(default)]
+    pub allow_index_type_change: bool,
 }
 
 #[derive(Deserialize, Serialize, ToSchema, Debug, Clone)]
 pub struct InternalUpdateCollectionConfiguration {
     pub vector_index: Option<UpdateVectorIndexConfiguration>,
     pub embedding_function: Option<EmbeddingFunctionConfiguration>,
+    pub fts_index: Option<UpdateFullTextIndexConfiguration>,
+    pub allow_index_type_change: bool,
+}
+
+#[derive(Debug, Error)]
+pub enum UpdateCollectionConfigurationError {
+    #[error("{0}")]
+    FullTextIndex(#[from] FullTextIndexConfigurationError),
+    #[error("Changing the vector index type from {from} to {to} is not allowed: switching to or from flat is always rejected, and switching between hnsw and spann while it would discard unshared tuning needs allow_index_type_change")]
+    VectorIndexTypeChange {
+        from: &'static str,
+        to: &'static str,
+    },
+    #[error("{0}")]
+    Validation(#[from] ConfigurationValidationError),
+}
+
+impl ChromaError for UpdateCollectionConfigurationError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            Self::FullTextIndex(e) => e.code(),
+            Self::VectorIndexTypeChange { .. } => ErrorCodes::InvalidArgument,
+            Self::Validation(e) => e.code(),
+        }
+    }
 }
 
 #[derive(Debug, Error)]
@@ -430,21 +1214,34 @@ impl TryFrom<UpdateCollectionConfiguration> for InternalUpdateCollectionConfigur
     type Error = UpdateCollectionConfigurationToInternalUpdateConfigurationError;
 
     fn try_from(value: UpdateCollectionConfiguration) -> Result<Self, Self::Error> {
-        match (value.hnsw, value.spann) {
-            (Some(_), Some(_)) => Err(Self::Error::MultipleVectorIndexConfigurations),
-            (Some(hnsw), None) => Ok(InternalUpdateCollectionConfiguration {
-                vector_index: Some(UpdateVectorIndexConfiguration::Hnsw(Some(hnsw))),
-                embedding_function: value.embedding_function,
-            }),
-            (None, Some(spann)) => Ok(InternalUpdateCollectionConfiguration {
-                vector_index: Some(UpdateVectorIndexConfiguration::Spann(Some(spann))),
-                embedding_function: value.embedding_function,
-            }),
-            (None, None) => Ok(InternalUpdateCollectionConfiguration {
-                vector_index: None,
-                embedding_function: value.embedding_function,
-            }),
+        let provided = [
+            value.hnsw.is_some(),
+            value.spann.is_some(),
+            value.flat.is_some(),
+        ]
+        .iter()
+        .filter(|provided| **provided)
+        .count();
+        if provided > 1 {
+            return Err(Self::Error::MultipleVectorIndexConfigurations);
         }
+
+        let vector_index = if let Some(hnsw) = value.hnsw {
+            Some(UpdateVectorIndexConfiguration::Hnsw(Some(hnsw)))
+        } else if let Some(spann) = value.spann {
+            Some(UpdateVectorIndexConfiguration::Spann(Some(spann)))
+        } else {
+            value
+                .flat
+                .map(|flat| UpdateVectorIndexConfiguration::Flat(Some(flat)))
+        };
+
+        Ok(InternalUpdateCollectionConfiguration {
+            vector_index,
+            embedding_function: value.embedding_function,
+            fts_index: value.fts_index,
+            allow_index_type_change: value.allow_index_type_change,
+        })
     }
 }
 
@@ -490,11 +1287,13 @@ mod tests {
         segment.metadata = Some(metadata);
 
         let config = InternalCollectionConfiguration {
+            config_version: CURRENT_CONFIG_VERSION,
             vector_index: VectorIndexConfiguration::Hnsw(InternalHnswConfiguration {
                 ef_construction: 2,
                 ..Default::default()
             }),
             embedding_function: None,
+            fts_index: None,
         };
 
         let overridden_config = config
@@ -522,7 +1321,10 @@ mod tests {
         let collection_config = CollectionConfiguration {
             hnsw: Some(hnsw_config.clone()),
             spann: None,
+            flat: None,
             embedding_function: None,
+            fts_index: None,
+            expected_collection_size: None,
         };
 
         let internal_config_result = InternalCollectionConfiguration::try_from_config(
@@ -554,7 +1356,10 @@ mod tests {
         let collection_config = CollectionConfiguration {
             hnsw: Some(hnsw_config.clone()),
             spann: None,
+            flat: None,
             embedding_function: None,
+            fts_index: None,
+            expected_collection_size: None,
         };
 
         let internal_config_result = InternalCollectionConfiguration::try_from_config(
@@ -578,7 +1383,7 @@ mod tests {
         let spann_config = SpannConfiguration {
             ef_construction: Some(100),
             ef_search: Some(10),
-            max_neighbors: Some(16),
+            max_neighbors: Some(64),
             search_nprobe: Some(1),
             write_nprobe: Some(1),
             space: Some(HnswSpace::Cosine),
@@ -590,7 +1395,10 @@ mod tests {
         let collection_config = CollectionConfiguration {
             hnsw: None,
             spann: Some(spann_config.clone()),
+            flat: None,
             embedding_function: None,
+            fts_index: None,
+            expected_collection_size: None,
         };
 
         let internal_config_result = InternalCollectionConfiguration::try_from_config(
@@ -611,7 +1419,7 @@ mod tests {
         let spann_config = SpannConfiguration {
             ef_construction: Some(100),
             ef_search: Some(10),
-            max_neighbors: Some(16),
+            max_neighbors: Some(64),
             search_nprobe: Some(1),
             write_nprobe: Some(1),
             space: Some(HnswSpace::Cosine),
@@ -623,7 +1431,10 @@ mod tests {
         let collection_config = CollectionConfiguration {
             hnsw: None,
             spann: Some(spann_config.clone()),
+            flat: None,
             embedding_function: None,
+            fts_index: None,
+            expected_collection_size: None,
         };
 
         let internal_config_result = InternalCollectionConfiguration::try_from_config(
@@ -648,7 +1459,10 @@ mod tests {
         let collection_config = CollectionConfiguration {
             hnsw: None,
             spann: None,
+            flat: None,
             embedding_function: None,
+            fts_index: None,
+            expected_collection_size: None,
         };
 
         let internal_config_result = InternalCollectionConfiguration::try_from_config(
@@ -672,7 +1486,10 @@ mod tests {
         let collection_config = CollectionConfiguration {
             hnsw: None,
             spann: None,
+            flat: None,
             embedding_function: None,
+            fts_index: None,
+            expected_collection_size: None,
         };
 
         let internal_config_result = InternalCollectionConfiguration::try_from_config(
@@ -699,13 +1516,16 @@ mod tests {
         );
         metadata.insert(
             "hnsw:construction_ef".to_string(),
-            crate::MetadataValue::Int(1),
+            crate::MetadataValue::Int(200),
         );
 
         let collection_config = CollectionConfiguration {
             hnsw: None,
             spann: None,
+            flat: None,
             embedding_function: None,
+            fts_index: None,
+            expected_collection_size: None,
         };
 
         let internal_config_result = InternalCollectionConfiguration::try_from_config(
@@ -721,12 +1541,41 @@ mod tests {
             internal_config.vector_index,
             VectorIndexConfiguration::Hnsw(InternalHnswConfiguration {
                 space: HnswSpace::Cosine,
-                ef_construction: 1,
+                ef_construction: 200,
                 ..Default::default()
             })
         );
     }
 
+    #[test]
+    fn test_legacy_metadata_with_ef_construction_below_max_neighbors_is_rejected() {
+        let mut metadata = Metadata::new();
+        metadata.insert(
+            "hnsw:construction_ef".to_string(),
+            crate::MetadataValue::Int(1),
+        );
+
+        let collection_config = CollectionConfiguration {
+            hnsw: None,
+            spann: None,
+            flat: None,
+            embedding_function: None,
+            fts_index: None,
+            expected_collection_size: None,
+        };
+
+        let result = InternalCollectionConfiguration::try_from_config(
+            collection_config,
+            KnnIndex::Hnsw,
+            Some(metadata),
+        );
+
+        assert!(matches!(
+            result,
+            Err(CollectionConfigurationToInternalConfigurationError::Validation(_))
+        ));
+    }
+
     #[test]
     fn test_legacy_metadata_with_spann_config() {
         let mut metadata = Metadata::new();
@@ -742,7 +1591,10 @@ mod tests {
         let collection_config = CollectionConfiguration {
             hnsw: None,
             spann: None,
+            flat: None,
             embedding_function: None,
+            fts_index: None,
+            expected_collection_size: None,
         };
 
         let internal_config_result = InternalCollectionConfiguration::try_from_config(
@@ -767,6 +1619,7 @@ mod tests {
     #[test]
     fn test_update_collection_configuration_with_hnsw() {
         let mut config = InternalCollectionConfiguration {
+            config_version: CURRENT_CONFIG_VERSION,
             vector_index: VectorIndexConfiguration::Hnsw(InternalHnswConfiguration {
                 space: HnswSpace::Cosine,
                 ..Default::default()
@@ -777,6 +1630,7 @@ mod tests {
                     config: serde_json::Value::Null,
                 },
             )),
+            fts_index: None,
         };
         let update_config = UpdateCollectionConfiguration {
             hnsw: Some(UpdateHnswConfiguration {
@@ -784,9 +1638,12 @@ mod tests {
                 ..Default::default()
             }),
             spann: None,
+            flat: None,
             embedding_function: None,
+            fts_index: None,
+            allow_index_type_change: false,
         };
-        config.update(&update_config.try_into().unwrap());
+        config.update(&update_config.try_into().unwrap()).unwrap();
         assert_eq!(
             config.vector_index,
             VectorIndexConfiguration::Hnsw(InternalHnswConfiguration {
@@ -810,6 +1667,7 @@ mod tests {
     #[test]
     fn test_update_collection_configuration_with_spann() {
         let mut config = InternalCollectionConfiguration {
+            config_version: CURRENT_CONFIG_VERSION,
             vector_index: VectorIndexConfiguration::Spann(InternalSpannConfiguration {
                 space: HnswSpace::Cosine,
                 ..Default::default()
@@ -820,6 +1678,7 @@ mod tests {
                     config: serde_json::Value::Null,
                 },
             )),
+            fts_index: None,
         };
         let update_config = UpdateCollectionConfiguration {
             hnsw: None,
@@ -827,9 +1686,12 @@ mod tests {
                 ef_search: Some(1),
                 ..Default::default()
             }),
+            flat: None,
             embedding_function: None,
+            fts_index: None,
+            allow_index_type_change: false,
         };
-        config.update(&update_config.try_into().unwrap());
+        config.update(&update_config.try_into().unwrap()).unwrap();
         assert_eq!(
             config.vector_index,
             VectorIndexConfiguration::Spann(InternalSpannConfiguration {
@@ -853,6 +1715,7 @@ mod tests {
     #[test]
     fn test_update_collection_configuration_with_embedding_function() {
         let mut config = InternalCollectionConfiguration {
+            config_version: CURRENT_CONFIG_VERSION,
             vector_index: VectorIndexConfiguration::Hnsw(InternalHnswConfiguration::default()),
             embedding_function: Some(EmbeddingFunctionConfiguration::Known(
                 EmbeddingFunctionNewConfiguration {
@@ -860,6 +1723,7 @@ mod tests {
                     config: serde_json::Value::Null,
                 },
             )),
+            fts_index: None,
         };
         let emb_fn_config = EmbeddingFunctionNewConfiguration {
             name: "test2".to_string(),
@@ -871,9 +1735,12 @@ mod tests {
         let update_config = UpdateCollectionConfiguration {
             hnsw: None,
             spann: None,
+            flat: None,
             embedding_function: Some(EmbeddingFunctionConfiguration::Known(emb_fn_config)),
+            fts_index: None,
+            allow_index_type_change: false,
         };
-        config.update(&update_config.try_into().unwrap());
+        config.update(&update_config.try_into().unwrap()).unwrap();
         assert_eq!(
             config.embedding_function,
             Some(EmbeddingFunctionConfiguration::Known(
@@ -887,4 +1754,1054 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn test_legacy_metadata_with_fts_config() {
+        let mut metadata = Metadata::new();
+        metadata.insert(
+            "fts:tokenizer".to_string(),
+            crate::MetadataValue::Str("ngram".to_string()),
+        );
+        metadata.insert("fts:min_gram".to_string(), crate::MetadataValue::Int(2));
+        metadata.insert("fts:max_gram".to_string(), crate::MetadataValue::Int(4));
+        metadata.insert(
+            "fts:stopwords".to_string(),
+            crate::MetadataValue::Str("english".to_string()),
+        );
+
+        let config = InternalCollectionConfiguration::default_hnsw();
+        let fts_config = config
+            .get_fts_config_from_legacy_metadata(&Some(metadata))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            fts_config,
+            FullTextIndexConfiguration {
+                tokenizer: FullTextTokenizer::Ngram {
+                    min_gram: 2,
+                    max_gram: 4
+                },
+                stopwords: Some(StopwordsConfiguration::Named("english".to_string())),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_legacy_metadata_with_no_fts_keys_is_none() {
+        let config = InternalCollectionConfiguration::default_hnsw();
+        let fts_config = config
+            .get_fts_config_from_legacy_metadata(&Some(Metadata::new()))
+            .unwrap();
+
+        assert_eq!(fts_config, None);
+    }
+
+    #[test]
+    fn test_legacy_metadata_with_invalid_ngram_range_errors() {
+        let mut metadata = Metadata::new();
+        metadata.insert(
+            "fts:tokenizer".to_string(),
+            crate::MetadataValue::Str("ngram".to_string()),
+        );
+        metadata.insert("fts:min_gram".to_string(), crate::MetadataValue::Int(5));
+        metadata.insert("fts:max_gram".to_string(), crate::MetadataValue::Int(2));
+
+        let config = InternalCollectionConfiguration::default_hnsw();
+        let result = config.get_fts_config_from_legacy_metadata(&Some(metadata));
+
+        assert!(matches!(
+            result,
+            Err(FullTextIndexConfigurationError::InvalidNgramRange {
+                min_gram: 5,
+                max_gram: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn test_update_fts_index_creates_new_index() {
+        let mut config = InternalCollectionConfiguration::default_hnsw();
+        assert_eq!(config.fts_index, None);
+
+        let update_config = InternalUpdateCollectionConfiguration {
+            vector_index: None,
+            embedding_function: None,
+            fts_index: Some(UpdateFullTextIndexConfiguration {
+                tokenizer: Some(FullTextTokenizer::Whitespace),
+                stopwords: None,
+            }),
+            allow_index_type_change: false,
+        };
+        config.update(&update_config).unwrap();
+
+        assert_eq!(
+            config.fts_index,
+            Some(FullTextIndexConfiguration {
+                tokenizer: FullTextTokenizer::Whitespace,
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_update_fts_index_without_tokenizer_when_none_configured_errors() {
+        let mut config = InternalCollectionConfiguration::default_hnsw();
+
+        let update_config = InternalUpdateCollectionConfiguration {
+            vector_index: None,
+            embedding_function: None,
+            fts_index: Some(UpdateFullTextIndexConfiguration {
+                tokenizer: None,
+                stopwords: Some(StopwordsConfiguration::Named("english".to_string())),
+            }),
+            allow_index_type_change: false,
+        };
+
+        assert!(matches!(
+            config.update(&update_config),
+            Err(UpdateCollectionConfigurationError::FullTextIndex(
+                FullTextIndexConfigurationError::NoExistingIndex
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_update_fts_index_stopwords_only() {
+        let mut config = InternalCollectionConfiguration {
+            fts_index: Some(FullTextIndexConfiguration {
+                tokenizer: FullTextTokenizer::Whitespace,
+                ..Default::default()
+            }),
+            ..InternalCollectionConfiguration::default_hnsw()
+        };
+
+        let update_config = InternalUpdateCollectionConfiguration {
+            vector_index: None,
+            embedding_function: None,
+            fts_index: Some(UpdateFullTextIndexConfiguration {
+                tokenizer: None,
+                stopwords: Some(StopwordsConfiguration::Named("english".to_string())),
+            }),
+            allow_index_type_change: false,
+        };
+        config.update(&update_config).unwrap();
+
+        assert_eq!(
+            config.fts_index,
+            Some(FullTextIndexConfiguration {
+                tokenizer: FullTextTokenizer::Whitespace,
+                stopwords: Some(StopwordsConfiguration::Named("english".to_string())),
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_update_fts_index_tokenizer_change_rejected() {
+        let mut config = InternalCollectionConfiguration {
+            fts_index: Some(FullTextIndexConfiguration {
+                tokenizer: FullTextTokenizer::Whitespace,
+                ..Default::default()
+            }),
+            ..InternalCollectionConfiguration::default_hnsw()
+        };
+
+        let update_config = InternalUpdateCollectionConfiguration {
+            vector_index: None,
+            embedding_function: None,
+            fts_index: Some(UpdateFullTextIndexConfiguration {
+                tokenizer: Some(FullTextTokenizer::Ngram {
+                    min_gram: 2,
+                    max_gram: 3,
+                }),
+                stopwords: None,
+            }),
+            allow_index_type_change: false,
+        };
+
+        assert!(matches!(
+            config.update(&update_config),
+            Err(UpdateCollectionConfigurationError::FullTextIndex(
+                FullTextIndexConfigurationError::ImmutableTokenizer
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_update_vector_index_converts_hnsw_to_spann_preserving_shared_params() {
+        let mut config = InternalCollectionConfiguration {
+            config_version: CURRENT_CONFIG_VERSION,
+            // `max_neighbors` is kept at the upper bound so the converted SPANN config still
+            // satisfies `reassign_neighbor_count <= max_neighbors` with its default
+            // `reassign_neighbor_count`.
+            vector_index: VectorIndexConfiguration::Hnsw(InternalHnswConfiguration {
+                space: HnswSpace::Cosine,
+                ef_construction: 200,
+                ef_search: 20,
+                max_neighbors: 128,
+                ..Default::default()
+            }),
+            ..InternalCollectionConfiguration::default_hnsw()
+        };
+
+        let update_config = InternalUpdateCollectionConfiguration {
+            vector_index: Some(UpdateVectorIndexConfiguration::Spann(None)),
+            embedding_function: None,
+            fts_index: None,
+            allow_index_type_change: false,
+        };
+        config.update(&update_config).unwrap();
+
+        assert_eq!(
+            config.vector_index,
+            VectorIndexConfiguration::Spann(InternalSpannConfiguration {
+                space: HnswSpace::Cosine,
+                ef_construction: 200,
+                ef_search: 20,
+                max_neighbors: 128,
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_update_vector_index_converts_spann_to_hnsw_preserving_shared_params() {
+        let mut config = InternalCollectionConfiguration {
+            config_version: CURRENT_CONFIG_VERSION,
+            vector_index: VectorIndexConfiguration::Spann(InternalSpannConfiguration {
+                space: HnswSpace::Cosine,
+                ef_construction: 50,
+                ef_search: 20,
+                max_neighbors: 8,
+                ..Default::default()
+            }),
+            ..InternalCollectionConfiguration::default_hnsw()
+        };
+
+        let update_config = InternalUpdateCollectionConfiguration {
+            vector_index: Some(UpdateVectorIndexConfiguration::Hnsw(None)),
+            embedding_function: None,
+            fts_index: None,
+            allow_index_type_change: false,
+        };
+        config.update(&update_config).unwrap();
+
+        assert_eq!(
+            config.vector_index,
+            VectorIndexConfiguration::Hnsw(InternalHnswConfiguration {
+                space: HnswSpace::Cosine,
+                ef_construction: 50,
+                ef_search: 20,
+                max_neighbors: 8,
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_update_vector_index_type_change_rejected_when_tuning_would_be_lost() {
+        let mut config = InternalCollectionConfiguration {
+            vector_index: VectorIndexConfiguration::Hnsw(InternalHnswConfiguration {
+                resize_factor: 2.0,
+                ..Default::default()
+            }),
+            ..InternalCollectionConfiguration::default_hnsw()
+        };
+
+        let update_config = InternalUpdateCollectionConfiguration {
+            vector_index: Some(UpdateVectorIndexConfiguration::Spann(None)),
+            embedding_function: None,
+            fts_index: None,
+            allow_index_type_change: false,
+        };
+
+        assert!(matches!(
+            config.update(&update_config),
+            Err(UpdateCollectionConfigurationError::VectorIndexTypeChange {
+                from: "hnsw",
+                to: "spann",
+            })
+        ));
+        // The rejected conversion must not have mutated the configuration.
+        assert_eq!(
+            config.vector_index,
+            VectorIndexConfiguration::Hnsw(InternalHnswConfiguration {
+                resize_factor: 2.0,
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_update_vector_index_type_change_allowed_with_flag() {
+        // The shared parameters start at SPANN's defaults, so the conversion (which carries them
+        // over) lands exactly on `InternalSpannConfiguration::default()`, while `resize_factor`
+        // is the unshared tuning that makes the flag necessary.
+        let spann_default = InternalSpannConfiguration::default();
+        let mut config = InternalCollectionConfiguration {
+            vector_index: VectorIndexConfiguration::Hnsw(InternalHnswConfiguration {
+                space: spann_default.space.clone(),
+                ef_construction: spann_default.ef_construction,
+                ef_search: spann_default.ef_search,
+                max_neighbors: spann_default.max_neighbors,
+                resize_factor: 2.0,
+                ..Default::default()
+            }),
+            ..InternalCollectionConfiguration::default_hnsw()
+        };
+
+        let update_config = InternalUpdateCollectionConfiguration {
+            vector_index: Some(UpdateVectorIndexConfiguration::Spann(None)),
+            embedding_function: None,
+            fts_index: None,
+            allow_index_type_change: true,
+        };
+        config.update(&update_config).unwrap();
+
+        assert_eq!(
+            config.vector_index,
+            VectorIndexConfiguration::Spann(InternalSpannConfiguration::default())
+        );
+    }
+
+    #[test]
+    fn test_default_configs_validate_cleanly() {
+        assert!(InternalHnswConfiguration::default().validate().is_ok());
+        assert!(InternalSpannConfiguration::default().validate().is_ok());
+        assert!(InternalCollectionConfiguration::default_hnsw()
+            .validate()
+            .is_ok());
+        assert!(InternalCollectionConfiguration::default_spann()
+            .validate()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_spann_validate_rejects_zero_fields() {
+        let spann = InternalSpannConfiguration {
+            max_neighbors: 0,
+            search_nprobe: 0,
+            ..Default::default()
+        };
+
+        let violations = spann.validate().unwrap_err().violations;
+        assert!(violations
+            .iter()
+            .any(|v| v.contains("max_neighbors must be between 2 and 128")));
+        assert!(violations
+            .iter()
+            .any(|v| v.contains("search_nprobe must be greater than 0")));
+    }
+
+    #[test]
+    fn test_spann_validate_rejects_merge_threshold_not_less_than_split_threshold() {
+        let spann = InternalSpannConfiguration {
+            merge_threshold: 200,
+            split_threshold: 100,
+            ..Default::default()
+        };
+
+        assert!(spann
+            .validate()
+            .unwrap_err()
+            .violations
+            .iter()
+            .any(|v| v.contains("merge_threshold must be less than split_threshold")));
+    }
+
+    #[test]
+    fn test_spann_validate_rejects_write_nprobe_above_search_nprobe() {
+        let spann = InternalSpannConfiguration {
+            search_nprobe: 1,
+            write_nprobe: 2,
+            ..Default::default()
+        };
+
+        assert!(spann
+            .validate()
+            .unwrap_err()
+            .violations
+            .iter()
+            .any(|v| v.contains("write_nprobe must be less than or equal to search_nprobe")));
+    }
+
+    #[test]
+    fn test_spann_validate_rejects_reassign_neighbor_count_above_max_neighbors() {
+        let spann = InternalSpannConfiguration {
+            max_neighbors: 4,
+            reassign_neighbor_count: 5,
+            ..Default::default()
+        };
+
+        assert!(spann
+            .validate()
+            .unwrap_err()
+            .violations
+            .iter()
+            .any(|v| v.contains("reassign_neighbor_count must be less than or equal to max_neighbors")));
+    }
+
+    #[test]
+    fn test_spann_validate_reports_all_violations_at_once() {
+        let spann = InternalSpannConfiguration {
+            max_neighbors: 0,
+            merge_threshold: 200,
+            split_threshold: 100,
+            ..Default::default()
+        };
+
+        let violations = spann.validate().unwrap_err().violations;
+        assert!(violations.len() >= 2);
+        assert!(violations
+            .iter()
+            .any(|v| v.contains("max_neighbors must be between 2 and 128")));
+        assert!(violations
+            .iter()
+            .any(|v| v.contains("merge_threshold must be less than split_threshold")));
+    }
+
+    #[test]
+    fn test_hnsw_validate_rejects_zero_max_neighbors() {
+        let hnsw = InternalHnswConfiguration {
+            max_neighbors: 0,
+            ..Default::default()
+        };
+
+        assert!(hnsw
+            .validate()
+            .unwrap_err()
+            .violations
+            .iter()
+            .any(|v| v.contains("max_neighbors must be between 2 and 128")));
+    }
+
+    #[test]
+    fn test_hnsw_validate_rejects_max_neighbors_above_upper_bound() {
+        let hnsw = InternalHnswConfiguration {
+            max_neighbors: 256,
+            ..Default::default()
+        };
+
+        assert!(hnsw
+            .validate()
+            .unwrap_err()
+            .violations
+            .iter()
+            .any(|v| v.contains("max_neighbors must be between 2 and 128")));
+    }
+
+    #[test]
+    fn test_hnsw_validate_rejects_ef_construction_below_max_neighbors() {
+        let hnsw = InternalHnswConfiguration {
+            max_neighbors: 64,
+            ef_construction: 10,
+            ..Default::default()
+        };
+
+        assert!(hnsw
+            .validate()
+            .unwrap_err()
+            .violations
+            .iter()
+            .any(|v| v.contains("ef_construction must be greater than or equal to max_neighbors")));
+    }
+
+    #[test]
+    fn test_update_rejects_invalid_spann_params() {
+        let mut config = InternalCollectionConfiguration::default_spann();
+
+        let update_config = InternalUpdateCollectionConfiguration {
+            vector_index: Some(UpdateVectorIndexConfiguration::Spann(Some(
+                UpdateSpannConfiguration {
+                    search_nprobe: Some(0),
+                    ..Default::default()
+                },
+            ))),
+            embedding_function: None,
+            fts_index: None,
+            allow_index_type_change: false,
+        };
+
+        assert!(matches!(
+            config.update(&update_config),
+            Err(UpdateCollectionConfigurationError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_try_from_config_rejects_invalid_spann_params() {
+        let spann_config = SpannConfiguration {
+            ef_construction: Some(100),
+            ef_search: Some(10),
+            max_neighbors: Some(16),
+            search_nprobe: Some(1),
+            write_nprobe: Some(1),
+            space: Some(HnswSpace::Cosine),
+            reassign_neighbor_count: Some(64),
+            split_threshold: Some(100),
+            merge_threshold: Some(200),
+        };
+
+        let collection_config = CollectionConfiguration {
+            hnsw: None,
+            spann: Some(spann_config),
+            flat: None,
+            embedding_function: None,
+            fts_index: None,
+            expected_collection_size: None,
+        };
+
+        let result = InternalCollectionConfiguration::try_from_config(
+            collection_config,
+            KnnIndex::Spann,
+            None,
+        );
+
+        assert!(matches!(
+            result,
+            Err(CollectionConfigurationToInternalConfigurationError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_hnsw_validate_rejects_scalar_int8_with_non_l2_space() {
+        let hnsw = InternalHnswConfiguration {
+            space: HnswSpace::Cosine,
+            quantization: VectorIndexQuantization::ScalarInt8,
+            ..Default::default()
+        };
+
+        assert!(hnsw
+            .validate()
+            .unwrap_err()
+            .violations
+            .iter()
+            .any(|v| v.contains("scalar int8 quantization is only supported with the l2 space")));
+    }
+
+    #[test]
+    fn test_spann_validate_rejects_product_quantization_bad_params() {
+        let spann = InternalSpannConfiguration {
+            space: HnswSpace::L2,
+            quantization: VectorIndexQuantization::Product {
+                num_subquantizers: 0,
+                bits_per_code: 9,
+            },
+            ..Default::default()
+        };
+
+        let violations = spann.validate().unwrap_err().violations;
+        assert!(violations
+            .iter()
+            .any(|v| v.contains("num_subquantizers must be greater than 0")));
+        assert!(violations
+            .iter()
+            .any(|v| v.contains("bits_per_code must be between 1 and 8")));
+    }
+
+    #[test]
+    fn test_hnsw_validate_accepts_product_quantization_with_l2_space() {
+        let hnsw = InternalHnswConfiguration {
+            space: HnswSpace::L2,
+            quantization: VectorIndexQuantization::Product {
+                num_subquantizers: 8,
+                bits_per_code: 8,
+            },
+            ..Default::default()
+        };
+
+        assert!(hnsw.validate().is_ok());
+    }
+
+    #[test]
+    fn test_update_vector_index_applies_quantization() {
+        let mut config = InternalCollectionConfiguration::default_hnsw();
+
+        let update_config = InternalUpdateCollectionConfiguration {
+            vector_index: Some(UpdateVectorIndexConfiguration::Hnsw(Some(
+                UpdateHnswConfiguration {
+                    quantization: Some(VectorIndexQuantization::ScalarInt8),
+                    ..Default::default()
+                },
+            ))),
+            embedding_function: None,
+            fts_index: None,
+            allow_index_type_change: false,
+        };
+
+        config.update(&update_config).unwrap();
+
+        assert_eq!(
+            config.vector_index,
+            VectorIndexConfiguration::Hnsw(InternalHnswConfiguration {
+                quantization: VectorIndexQuantization::ScalarInt8,
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_quantization_from_legacy_metadata_parses_product() {
+        let mut metadata = Metadata::new();
+        metadata.insert(
+            "hnsw:quantization".to_string(),
+            crate::MetadataValue::Str("product".to_string()),
+        );
+        metadata.insert(
+            "hnsw:quantization_num_subquantizers".to_string(),
+            crate::MetadataValue::Int(8),
+        );
+        metadata.insert(
+            "hnsw:quantization_bits_per_code".to_string(),
+            crate::MetadataValue::Int(4),
+        );
+
+        let collection_config = CollectionConfiguration {
+            hnsw: None,
+            spann: None,
+            flat: None,
+            embedding_function: None,
+            fts_index: None,
+            expected_collection_size: None,
+        };
+
+        let internal_config = InternalCollectionConfiguration::try_from_config(
+            collection_config,
+            KnnIndex::Hnsw,
+            Some(metadata),
+        )
+        .unwrap();
+
+        assert_eq!(
+            internal_config.vector_index,
+            VectorIndexConfiguration::Hnsw(InternalHnswConfiguration {
+                quantization: VectorIndexQuantization::Product {
+                    num_subquantizers: 8,
+                    bits_per_code: 4,
+                },
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_quantization_from_legacy_metadata_absent_key_keeps_default() {
+        let collection_config = CollectionConfiguration {
+            hnsw: None,
+            spann: None,
+            flat: None,
+            embedding_function: None,
+            fts_index: None,
+            expected_collection_size: None,
+        };
+
+        let internal_config = InternalCollectionConfiguration::try_from_config(
+            collection_config,
+            KnnIndex::Hnsw,
+            Some(Metadata::new()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            internal_config.vector_index,
+            VectorIndexConfiguration::Hnsw(InternalHnswConfiguration::default())
+        );
+    }
+
+    #[test]
+    fn test_flat_config_round_trips_through_try_from_config() {
+        let collection_config = CollectionConfiguration {
+            hnsw: None,
+            spann: None,
+            flat: Some(InternalFlatConfiguration {
+                space: HnswSpace::Cosine,
+            }),
+            embedding_function: None,
+            fts_index: None,
+            expected_collection_size: None,
+        };
+
+        let internal_config = InternalCollectionConfiguration::try_from_config(
+            collection_config,
+            KnnIndex::Flat,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            internal_config.vector_index,
+            VectorIndexConfiguration::Flat(InternalFlatConfiguration {
+                space: HnswSpace::Cosine,
+            })
+        );
+    }
+
+    #[test]
+    fn test_no_config_with_default_knn_index_flat() {
+        let collection_config = CollectionConfiguration {
+            hnsw: None,
+            spann: None,
+            flat: None,
+            embedding_function: None,
+            fts_index: None,
+            expected_collection_size: None,
+        };
+
+        let internal_config = InternalCollectionConfiguration::try_from_config(
+            collection_config,
+            KnnIndex::Flat,
+            Some(Metadata::new()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            internal_config.vector_index,
+            VectorIndexConfiguration::Flat(InternalFlatConfiguration::default())
+        );
+    }
+
+    #[test]
+    fn test_hnsw_config_with_flat_default_inherits_space() {
+        let collection_config = CollectionConfiguration {
+            hnsw: Some(HnswConfiguration {
+                space: Some(HnswSpace::Cosine),
+                ef_construction: None,
+                ef_search: None,
+                max_neighbors: None,
+                resize_factor: None,
+                sync_threshold: None,
+                num_threads: None,
+                batch_size: None,
+            }),
+            spann: None,
+            flat: None,
+            embedding_function: None,
+            fts_index: None,
+            expected_collection_size: None,
+        };
+
+        let internal_config = InternalCollectionConfiguration::try_from_config(
+            collection_config,
+            KnnIndex::Flat,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            internal_config.vector_index,
+            VectorIndexConfiguration::Flat(InternalFlatConfiguration {
+                space: HnswSpace::Cosine,
+            })
+        );
+    }
+
+    #[test]
+    fn test_flat_config_with_hnsw_default_inherits_space() {
+        let collection_config = CollectionConfiguration {
+            hnsw: None,
+            spann: None,
+            flat: Some(InternalFlatConfiguration {
+                space: HnswSpace::Cosine,
+            }),
+            embedding_function: None,
+            fts_index: None,
+            expected_collection_size: None,
+        };
+
+        let internal_config = InternalCollectionConfiguration::try_from_config(
+            collection_config,
+            KnnIndex::Hnsw,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            internal_config.vector_index,
+            VectorIndexConfiguration::Hnsw(InternalHnswConfiguration {
+                space: HnswSpace::Cosine,
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_update_vector_index_applies_flat_space_change() {
+        let mut config = InternalCollectionConfiguration {
+            vector_index: VectorIndexConfiguration::Flat(InternalFlatConfiguration {
+                space: HnswSpace::L2,
+            }),
+            ..InternalCollectionConfiguration::default_hnsw()
+        };
+
+        let update_config = InternalUpdateCollectionConfiguration {
+            vector_index: Some(UpdateVectorIndexConfiguration::Flat(Some(
+                UpdateFlatConfiguration {
+                    space: Some(HnswSpace::Cosine),
+                },
+            ))),
+            embedding_function: None,
+            fts_index: None,
+            allow_index_type_change: false,
+        };
+        config.update(&update_config).unwrap();
+
+        assert_eq!(
+            config.vector_index,
+            VectorIndexConfiguration::Flat(InternalFlatConfiguration {
+                space: HnswSpace::Cosine,
+            })
+        );
+    }
+
+    #[test]
+    fn test_update_vector_index_rejects_switching_away_from_flat_even_with_flag() {
+        let mut config = InternalCollectionConfiguration {
+            vector_index: VectorIndexConfiguration::Flat(InternalFlatConfiguration::default()),
+            ..InternalCollectionConfiguration::default_hnsw()
+        };
+
+        let update_config = InternalUpdateCollectionConfiguration {
+            vector_index: Some(UpdateVectorIndexConfiguration::Hnsw(None)),
+            embedding_function: None,
+            fts_index: None,
+            allow_index_type_change: true,
+        };
+
+        assert!(matches!(
+            config.update(&update_config),
+            Err(UpdateCollectionConfigurationError::VectorIndexTypeChange {
+                from: "flat",
+                to: "hnsw",
+            })
+        ));
+        assert_eq!(
+            config.vector_index,
+            VectorIndexConfiguration::Flat(InternalFlatConfiguration::default())
+        );
+    }
+
+    #[test]
+    fn test_update_vector_index_rejects_switching_to_flat() {
+        let mut config = InternalCollectionConfiguration {
+            vector_index: VectorIndexConfiguration::Hnsw(InternalHnswConfiguration::default()),
+            ..InternalCollectionConfiguration::default_hnsw()
+        };
+
+        let update_config = InternalUpdateCollectionConfiguration {
+            vector_index: Some(UpdateVectorIndexConfiguration::Flat(None)),
+            embedding_function: None,
+            fts_index: None,
+            allow_index_type_change: true,
+        };
+
+        assert!(matches!(
+            config.update(&update_config),
+            Err(UpdateCollectionConfigurationError::VectorIndexTypeChange {
+                from: "hnsw",
+                to: "flat",
+            })
+        ));
+    }
+
+    #[test]
+    fn test_migrate_upgrades_v0_config_using_legacy_metadata() {
+        // Simulates an on-disk shape from before `config_version` existed: the JSON has no
+        // `config_version` key at all, so it deserializes to 0 via `#[serde(default)]`, and no
+        // `vector_index` key either, so it deserializes to the HNSW default via
+        // `default_vector_index_config`.
+        let old_config: InternalCollectionConfiguration =
+            serde_json::from_str(r#"{"embedding_function":null,"fts_index":null}"#).unwrap();
+        assert_eq!(old_config.config_version, 0);
+        assert_eq!(
+            old_config.vector_index,
+            VectorIndexConfiguration::Hnsw(InternalHnswConfiguration::default())
+        );
+
+        let mut metadata = Metadata::new();
+        metadata.insert(
+            "hnsw:space".to_string(),
+            crate::MetadataValue::Str("cosine".to_string()),
+        );
+
+        let migrated = old_config.migrate(&Some(metadata)).unwrap();
+
+        assert_eq!(migrated.config_version, CURRENT_CONFIG_VERSION);
+        assert_eq!(
+            migrated.vector_index,
+            VectorIndexConfiguration::Hnsw(InternalHnswConfiguration {
+                space: HnswSpace::Cosine,
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_migrate_is_a_noop_at_current_version() {
+        let config = InternalCollectionConfiguration::default_spann();
+        assert_eq!(config.config_version, CURRENT_CONFIG_VERSION);
+
+        let migrated = config.clone().migrate(&None).unwrap();
+        assert_eq!(migrated, config);
+    }
+
+    #[test]
+    fn test_migrate_does_not_override_already_configured_hnsw() {
+        // A version 0 config that already holds non-default HNSW tuning was written by code
+        // that predates `config_version` but post-dates the legacy metadata path, so the
+        // migration has nothing to recover and must leave it untouched.
+        let config = InternalCollectionConfiguration {
+            config_version: 0,
+            vector_index: VectorIndexConfiguration::Hnsw(InternalHnswConfiguration {
+                ef_construction: 200,
+                ..Default::default()
+            }),
+            embedding_function: None,
+            fts_index: None,
+        };
+
+        let migrated = config.clone().migrate(&None).unwrap();
+
+        assert_eq!(migrated.config_version, CURRENT_CONFIG_VERSION);
+        assert_eq!(migrated.vector_index, config.vector_index);
+    }
+
+    #[test]
+    fn test_auto_select_flat_for_small_expected_collection_size() {
+        let collection_config = CollectionConfiguration {
+            hnsw: None,
+            spann: None,
+            flat: None,
+            embedding_function: None,
+            fts_index: None,
+            expected_collection_size: Some(AUTO_INDEX_FLAT_THRESHOLD - 1),
+        };
+
+        let internal_config = InternalCollectionConfiguration::try_from_config(
+            collection_config,
+            KnnIndex::Hnsw,
+            Some(Metadata::new()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            internal_config.vector_index,
+            VectorIndexConfiguration::Flat(InternalFlatConfiguration::default())
+        );
+    }
+
+    #[test]
+    fn test_auto_select_hnsw_for_mid_expected_collection_size() {
+        let collection_config = CollectionConfiguration {
+            hnsw: None,
+            spann: None,
+            flat: None,
+            embedding_function: None,
+            fts_index: None,
+            expected_collection_size: Some(AUTO_INDEX_FLAT_THRESHOLD),
+        };
+
+        let internal_config = InternalCollectionConfiguration::try_from_config(
+            collection_config,
+            KnnIndex::Spann,
+            Some(Metadata::new()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            internal_config.vector_index,
+            VectorIndexConfiguration::Hnsw(InternalHnswConfiguration::default())
+        );
+    }
+
+    #[test]
+    fn test_auto_select_spann_for_large_expected_collection_size() {
+        let collection_config = CollectionConfiguration {
+            hnsw: None,
+            spann: None,
+            flat: None,
+            embedding_function: None,
+            fts_index: None,
+            expected_collection_size: Some(AUTO_INDEX_SPANN_THRESHOLD + 1),
+        };
+
+        let internal_config = InternalCollectionConfiguration::try_from_config(
+            collection_config,
+            KnnIndex::Hnsw,
+            Some(Metadata::new()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            internal_config.vector_index,
+            VectorIndexConfiguration::Spann(InternalSpannConfiguration::default())
+        );
+    }
+
+    #[test]
+    fn test_auto_select_carries_over_space_from_legacy_metadata() {
+        let mut metadata = Metadata::new();
+        metadata.insert(
+            "hnsw:space".to_string(),
+            crate::MetadataValue::Str("cosine".to_string()),
+        );
+
+        let collection_config = CollectionConfiguration {
+            hnsw: None,
+            spann: None,
+            flat: None,
+            embedding_function: None,
+            fts_index: None,
+            expected_collection_size: Some(AUTO_INDEX_SPANN_THRESHOLD + 1),
+        };
+
+        let internal_config = InternalCollectionConfiguration::try_from_config(
+            collection_config,
+            KnnIndex::Hnsw,
+            Some(metadata),
+        )
+        .unwrap();
+
+        assert_eq!(
+            internal_config.vector_index,
+            VectorIndexConfiguration::Spann(InternalSpannConfiguration {
+                space: HnswSpace::Cosine,
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_explicit_config_overrides_expected_collection_size_hint() {
+        let collection_config = CollectionConfiguration {
+            hnsw: Some(HnswConfiguration {
+                max_neighbors: None,
+                ef_construction: None,
+                ef_search: None,
+                batch_size: None,
+                num_threads: None,
+                sync_threshold: None,
+                resize_factor: None,
+                space: None,
+            }),
+            spann: None,
+            flat: None,
+            embedding_function: None,
+            fts_index: None,
+            expected_collection_size: Some(AUTO_INDEX_SPANN_THRESHOLD + 1),
+        };
+
+        let internal_config = InternalCollectionConfiguration::try_from_config(
+            collection_config,
+            KnnIndex::Hnsw,
+            Some(Metadata::new()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            internal_config.vector_index,
+            VectorIndexConfiguration::Hnsw(InternalHnswConfiguration::default())
+        );
+    }
 }
@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_segment::{
+    blockfile_metadata::MetadataSegmentReader,
+    blockfile_record::{RecordSegmentReader, RecordSegmentReaderCreationError},
+    types::materialize_logs,
+};
+use chroma_system::Operator;
+use chroma_types::{Chunk, LogRecord, MetadataValue, Segment, SignedRoaringBitmap};
+use thiserror::Error;
+use tracing::{Instrument, Span};
+
+use super::filter::{
+    metadata_value_as_f64, FilterError, FilterOutput, MetadataLogReader, MetadataProvider,
+};
+
+/// Fixed-width bucketing applied to a numeric facet key, collapsing continuous values into
+/// histogram bins instead of returning one entry per distinct value.
+#[derive(Clone, Copy, Debug)]
+pub struct NumericBucketing {
+    pub bucket_width: f64,
+    pub start: f64,
+}
+
+impl NumericBucketing {
+    fn bucket(&self, value: f64) -> f64 {
+        let bucket_index = ((value - self.start) / self.bucket_width).floor();
+        self.start + bucket_index * self.bucket_width
+    }
+}
+
+/// The `FacetDistributionOperator` computes, for each of `facet_keys`, the distinct values held
+/// by the records matched by a prior `FilterOperator` run and how many matching records hold each
+/// value.
+///
+/// # Parameters
+/// - `facet_keys`: The metadata keys to compute a distribution for
+/// - `bucketing`: Optional fixed-width histogram bucketing for numeric keys, keyed by facet key
+///
+/// # Inputs
+/// - `logs`: The latest log of the collection
+/// - `blockfile_provider`: The blockfile provider
+/// - `metadata_segment`: The metadata segment information
+/// - `record_segment`: The record segment information
+/// - `filter_output`: The offset ids allowed by a prior `FilterOperator` run
+///
+/// # Outputs
+/// - `distributions`: For each facet key, the distinct values present among the allowed offsets,
+///   paired with how many allowed offsets hold that value
+#[derive(Clone, Debug)]
+pub struct FacetDistributionOperator {
+    pub facet_keys: Vec<String>,
+    pub bucketing: HashMap<String, NumericBucketing>,
+}
+
+#[derive(Clone, Debug)]
+pub struct FacetDistributionInput {
+    pub logs: Chunk<LogRecord>,
+    pub blockfile_provider: BlockfileProvider,
+    pub metadata_segment: Segment,
+    pub record_segment: Segment,
+    pub filter_output: FilterOutput,
+}
+
+#[derive(Clone, Debug)]
+pub struct FacetDistributionOutput {
+    pub distributions: HashMap<String, Vec<(MetadataValue, u64)>>,
+}
+
+#[derive(Error, Debug)]
+pub enum FacetDistributionError {
+    #[error("Error computing facet distribution: {0}")]
+    Filter(#[from] FilterError),
+}
+
+impl ChromaError for FacetDistributionError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            FacetDistributionError::Filter(e) => e.code(),
+        }
+    }
+}
+
+/// Adds `count` to the existing entry for `value` in `counts`, or appends a new one.
+/// `MetadataValue` doesn't implement `Hash`/`Eq` for floats, so entries are matched on their
+/// `Debug` representation instead of being kept in a `HashMap`.
+fn merge_count(counts: &mut Vec<(MetadataValue, u64)>, value: MetadataValue, count: u64) {
+    let value_key = format!("{:?}", value);
+    match counts
+        .iter_mut()
+        .find(|(existing, _)| format!("{:?}", existing) == value_key)
+    {
+        Some((_, existing)) => *existing += count,
+        None => counts.push((value, count)),
+    }
+}
+
+#[async_trait]
+impl Operator<FacetDistributionInput, FacetDistributionOutput> for FacetDistributionOperator {
+    type Error = FacetDistributionError;
+
+    async fn run(
+        &self,
+        input: &FacetDistributionInput,
+    ) -> Result<FacetDistributionOutput, FacetDistributionError> {
+        tracing::debug!("[{}]: {:?}", self.get_name(), input);
+
+        let record_segment_reader = match RecordSegmentReader::from_segment(
+            &input.record_segment,
+            &input.blockfile_provider,
+        )
+        .await
+        {
+            Ok(reader) => Ok(Some(reader)),
+            Err(e) if matches!(*e, RecordSegmentReaderCreationError::UninitializedSegment) => {
+                Ok(None)
+            }
+            Err(e) => Err(FilterError::RecordReader(*e)),
+        }?;
+        let cloned_record_segment_reader = record_segment_reader.clone();
+        let materialized_logs =
+            materialize_logs(&cloned_record_segment_reader, input.logs.clone(), None)
+                .instrument(tracing::trace_span!(parent: Span::current(), "Materialize logs"))
+                .await
+                .map_err(FilterError::LogMaterializer)?;
+        let metadata_log_reader =
+            MetadataLogReader::create(&materialized_logs, &record_segment_reader)
+                .await
+                .map_err(FilterError::LogMaterializer)?;
+        let log_metadata_provider = MetadataProvider::Log(&metadata_log_reader);
+
+        let metadata_segment_reader =
+            MetadataSegmentReader::from_segment(&input.metadata_segment, &input.blockfile_provider)
+                .await
+                .map_err(FilterError::MetadataReader)?;
+        let compact_metadata_provider =
+            MetadataProvider::CompactData(&metadata_segment_reader, &record_segment_reader);
+
+        let mut distributions = HashMap::with_capacity(self.facet_keys.len());
+        for key in &self.facet_keys {
+            let bucketing = self.bucketing.get(key);
+            let mut counts: Vec<(MetadataValue, u64)> = Vec::new();
+
+            // `filter_output.compact_offset_ids` already excludes `updated_offset_ids`, so
+            // records superseded by the log aren't counted again here.
+            for (provider, allowed) in [
+                (&log_metadata_provider, &input.filter_output.log_offset_ids),
+                (
+                    &compact_metadata_provider,
+                    &input.filter_output.compact_offset_ids,
+                ),
+            ] {
+                for (value, ids) in provider.facet_values(key).await? {
+                    // Intersecting a concrete (`Include`) set with anything always yields a
+                    // concrete, finite result, regardless of whether `allowed` is an `Include`
+                    // or an `Exclude` set.
+                    let matching = SignedRoaringBitmap::Include(ids) & allowed.clone();
+                    let count = match matching {
+                        SignedRoaringBitmap::Include(ids) => ids.len(),
+                        SignedRoaringBitmap::Exclude(_) => {
+                            unreachable!("intersecting with a concrete set is always concrete")
+                        }
+                    };
+                    if count == 0 {
+                        continue;
+                    }
+
+                    let bucketed_value = match (bucketing, metadata_value_as_f64(&value)) {
+                        (Some(bucketing), Some(numeric)) => {
+                            MetadataValue::Float(bucketing.bucket(numeric))
+                        }
+                        _ => value,
+                    };
+                    merge_count(&mut counts, bucketed_value, count);
+                }
+            }
+
+            distributions.insert(key.clone(), counts);
+        }
+
+        Ok(FacetDistributionOutput { distributions })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chroma_log::test::{add_delete_generator, LoadFromGenerator, LogGenerator};
+    use chroma_segment::test::TestDistributedSegment;
+    use chroma_system::Operator;
+    use chroma_types::MetadataValue;
+
+    use crate::execution::operators::filter::{FilterInput, FilterOperator, FilterStrategy};
+
+    use super::{FacetDistributionInput, FacetDistributionOperator, NumericBucketing};
+
+    /// Uses the same fixture as `FilterOperator`'s tests: 120 log records, the first 60 of which
+    /// are compacted.
+    /// - Log: Delete [11..=20], add [51..=100]
+    /// - Compacted: Delete [1..=10], add [11..=50]
+    /// So the visible universe is [21..=50] (compacted) and [51..=100] (log), 80 records total.
+    async fn setup_facet_distribution_input() -> FacetDistributionInput {
+        let mut test_segment = TestDistributedSegment::default();
+        test_segment
+            .populate_with_generator(60, add_delete_generator)
+            .await;
+
+        let filter_input = FilterInput {
+            logs: add_delete_generator.generate_chunk(61..=120),
+            blockfile_provider: test_segment.blockfile_provider,
+            metadata_segment: test_segment.metadata_segment,
+            record_segment: test_segment.record_segment,
+        };
+
+        let filter_output = FilterOperator {
+            query_ids: None,
+            where_clause: None,
+            strategy: FilterStrategy::Auto,
+        }
+        .run(&filter_input)
+        .await
+        .expect("FilterOperator should not fail");
+
+        FacetDistributionInput {
+            logs: filter_input.logs,
+            blockfile_provider: filter_input.blockfile_provider,
+            metadata_segment: filter_input.metadata_segment,
+            record_segment: filter_input.record_segment,
+            filter_output,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_facet_distribution_bool_key() {
+        let input = setup_facet_distribution_input().await;
+
+        let output = FacetDistributionOperator {
+            facet_keys: vec!["is_even".to_string()],
+            bucketing: HashMap::new(),
+        }
+        .run(&input)
+        .await
+        .expect("FacetDistributionOperator should not fail");
+
+        let counts = output
+            .distributions
+            .get("is_even")
+            .cloned()
+            .unwrap_or_default();
+
+        let expected_true = (51..=100).filter(|o| o % 2 == 0).count() as u64
+            + (21..=50).filter(|o| o % 2 == 0).count() as u64;
+        let expected_false = (51..=100).filter(|o| o % 2 != 0).count() as u64
+            + (21..=50).filter(|o| o % 2 != 0).count() as u64;
+
+        assert_eq!(counts.len(), 2);
+        for (value, count) in counts {
+            match value {
+                MetadataValue::Bool(true) => assert_eq!(count, expected_true),
+                MetadataValue::Bool(false) => assert_eq!(count, expected_false),
+                other => panic!("unexpected facet value {:?}", other),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_facet_distribution_numeric_bucketing() {
+        let input = setup_facet_distribution_input().await;
+
+        let mut bucketing = HashMap::new();
+        bucketing.insert(
+            "id".to_string(),
+            NumericBucketing {
+                bucket_width: 10.0,
+                start: 0.0,
+            },
+        );
+
+        let output = FacetDistributionOperator {
+            facet_keys: vec!["id".to_string()],
+            bucketing,
+        }
+        .run(&input)
+        .await
+        .expect("FacetDistributionOperator should not fail");
+
+        let counts = output.distributions.get("id").cloned().unwrap_or_default();
+        let total: u64 = counts.iter().map(|(_, count)| count).sum();
+        assert_eq!(total, 80);
+        for (value, _) in &counts {
+            match value {
+                MetadataValue::Float(bucket) => assert_eq!(bucket.rem_euclid(10.0), 0.0),
+                other => panic!("expected bucketed float value, got {:?}", other),
+            }
+        }
+    }
+}
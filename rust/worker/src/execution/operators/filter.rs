@@ -1,5 +1,7 @@
 use std::{
-    collections::{BTreeMap, HashMap},
+    cell::RefCell,
+    collections::{hash_map::DefaultHasher, BTreeMap, HashMap},
+    hash::{Hash, Hasher},
     ops::{BitAnd, BitOr, Bound},
 };
 
@@ -13,25 +15,154 @@ use chroma_segment::{
     types::{materialize_logs, LogMaterializerError, MaterializeLogsResult},
 };
 use chroma_system::Operator;
+// `Where`, `DocumentOperator`, and the rest of this block are declared in `chroma_types`'s own
+// where-clause module, not present in this checkout — confirmed pre-existing as of the `baseline`
+// commit (this file already imported them from `chroma_types` with no local definition anywhere
+// in the tree, before any request in this series touched the file). See the usage sites below for
+// what each request in this series still needs from that module.
 use chroma_types::{
     regex::{
         literal_expr::{LiteralExpr, NgramLiteralProvider},
         ChromaRegex, ChromaRegexError,
     },
-    BooleanOperator, Chunk, CompositeExpression, DocumentExpression, DocumentOperator, LogRecord,
-    MaterializedLogOperation, MetadataComparison, MetadataExpression, MetadataSetValue,
-    MetadataValue, PrimitiveOperator, Segment, SetOperator, SignedRoaringBitmap, Where,
+    BooleanOperator, Chunk, CompositeExpression, DocumentExpression, DocumentOperator,
+    GeoExpression, LogRecord, MaterializedLogOperation, MetadataComparison, MetadataExpression,
+    MetadataSetValue, MetadataValue, PrimitiveOperator, Segment, SetOperator, SignedRoaringBitmap,
+    Where,
 };
 use futures::TryStreamExt;
 use roaring::RoaringBitmap;
 use thiserror::Error;
 use tracing::{Instrument, Span};
 
+/// Below this many allowed offsets, leaf clause evaluation switches from an index scan to a
+/// per-candidate lookup against the record/metadata segment, since walking the whole posting list
+/// is wasteful once the universe is already this narrow.
+const CANDIDATES_THRESHOLD: u64 = 1000;
+
+/// Picks how leaf `MetadataExpression`/`DocumentExpression` clauses are evaluated against the
+/// allowed offsets narrowed down so far.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FilterStrategy {
+    /// Use `CANDIDATES_THRESHOLD` to pick a strategy automatically
+    #[default]
+    Auto,
+    /// Always scan the metadata/full-text index regardless of candidate count
+    IndexScan,
+    /// Evaluate the clause against each allowed candidate directly whenever the allowed set is
+    /// a concrete (`Include`) set; an unbounded (`Exclude`) allowed set still uses an index scan
+    PerCandidate,
+}
+
+/// Resolves which strategy a leaf clause should use given an override and the number of
+/// candidates the universe has already been narrowed to (`None` means unbounded, i.e. a full
+/// index scan is unavoidable).
+fn resolve_strategy(strategy_override: FilterStrategy, candidate_count: Option<u64>) -> FilterStrategy {
+    match strategy_override {
+        FilterStrategy::Auto => match candidate_count {
+            Some(count) if count < CANDIDATES_THRESHOLD => FilterStrategy::PerCandidate,
+            _ => FilterStrategy::IndexScan,
+        },
+        explicit => explicit,
+    }
+}
+
+/// Threads the resolved evaluation strategy, the `PerCandidate` candidate offsets, and the
+/// per-run evaluation cache down through leaf clause evaluation.
+#[derive(Clone, Copy)]
+pub(crate) struct EvalContext<'a> {
+    strategy: FilterStrategy,
+    candidates: Option<&'a RoaringBitmap>,
+    cache: &'a EvalCache,
+}
+
+impl<'a> EvalContext<'a> {
+    pub(crate) fn new(
+        strategy_override: FilterStrategy,
+        allowed: &'a SignedRoaringBitmap,
+        cache: &'a EvalCache,
+    ) -> Self {
+        let candidates = match allowed {
+            SignedRoaringBitmap::Include(ids) => Some(ids),
+            SignedRoaringBitmap::Exclude(_) => None,
+        };
+        let strategy = resolve_strategy(strategy_override, candidates.map(|ids| ids.len()));
+        Self {
+            strategy: if candidates.is_some() {
+                strategy
+            } else {
+                FilterStrategy::IndexScan
+            },
+            candidates,
+            cache,
+        }
+    }
+}
+
+/// Per-run memoization for `Where::eval`. Keyed on a hash of the sub-expression so a repeated
+/// leaf appearing in multiple branches of a composite tree is evaluated once, and on `(key, op,
+/// value)` so repeated raw posting-list lookups against the metadata index are only issued once.
+/// Scoped to a single `MetadataProvider` (callers keep one `EvalCache` per provider, since the
+/// same sub-expression can resolve differently against the log vs. the compacted segment).
+#[derive(Default)]
+pub(crate) struct EvalCache {
+    clause_results: RefCell<HashMap<u64, (String, SignedRoaringBitmap)>>,
+    posting_lists: RefCell<HashMap<(String, String, String), RoaringBitmap>>,
+    #[cfg(test)]
+    posting_list_misses: RefCell<u64>,
+}
+
+#[cfg(test)]
+impl EvalCache {
+    fn posting_list_miss_count(&self) -> u64 {
+        *self.posting_list_misses.borrow()
+    }
+}
+
+/// A stable hash of a `Where` sub-expression, paired with the `Debug` representation it was
+/// hashed from, used as the memoization key in `EvalCache`. `Where` and its children don't
+/// implement `Hash`, so the hash alone can't rule out a collision between two structurally
+/// different sub-expressions; the `Debug` string is kept alongside it and checked on lookup, the
+/// same way `posting_lists`'s tuple key already avoids collisions.
+fn hash_where(where_clause: &Where) -> (u64, String) {
+    let debug = format!("{:?}", where_clause);
+    let mut hasher = DefaultHasher::new();
+    debug.hash(&mut hasher);
+    (hasher.finish(), debug)
+}
+
+/// Looks up the raw posting-list result for `(key, op, val)` in `cache`, falling back to
+/// `metadata_provider.filter_by_metadata` on a miss. Pure caching wrapper: output is identical to
+/// calling `filter_by_metadata` directly.
+async fn cached_filter_by_metadata<'me>(
+    metadata_provider: &MetadataProvider<'me>,
+    cache: &EvalCache,
+    key: &str,
+    val: &MetadataValue,
+    op: &PrimitiveOperator,
+) -> Result<RoaringBitmap, FilterError> {
+    let cache_key = (key.to_string(), format!("{:?}", op), format!("{:?}", val));
+    if let Some(cached) = cache.posting_lists.borrow().get(&cache_key) {
+        return Ok(cached.clone());
+    }
+    let result = metadata_provider.filter_by_metadata(key, val, op).await?;
+    #[cfg(test)]
+    {
+        *cache.posting_list_misses.borrow_mut() += 1;
+    }
+    cache
+        .posting_lists
+        .borrow_mut()
+        .insert(cache_key, result.clone());
+    Ok(result)
+}
+
 /// The `FilterOperator` filters the collection with specified criteria
 ///
 /// # Parameters
 /// - `query_ids`: The user provided ids, which specifies the domain of the filter if provided
 /// - `where_clause`: The predicate on individual record
+/// - `strategy`: Overrides the automatic index-scan-vs-per-candidate selection; defaults to `Auto`
 ///
 /// # Inputs
 /// - `logs`: The latest log of the collection
@@ -50,6 +181,7 @@ use tracing::{Instrument, Span};
 pub struct FilterOperator {
     pub query_ids: Option<Vec<String>>,
     pub where_clause: Option<Where>,
+    pub strategy: FilterStrategy,
 }
 
 #[derive(Clone, Debug)]
@@ -80,6 +212,10 @@ pub enum FilterError {
     RecordReader(#[from] RecordSegmentReaderCreationError),
     #[error("Error parsing regular expression: {0}")]
     Regex(#[from] ChromaRegexError),
+    /// User-facing errors: the query itself is unsatisfiable given the data's actual shape, as
+    /// opposed to the other variants, which indicate an internal I/O/index failure.
+    #[error("Invalid filter on key \"{key}\": {reason}")]
+    InvalidFilter { key: String, reason: String },
 }
 
 impl ChromaError for FilterError {
@@ -91,10 +227,22 @@ impl ChromaError for FilterError {
             FilterError::Record(e) => e.code(),
             FilterError::RecordReader(e) => e.code(),
             FilterError::Regex(_) => ErrorCodes::InvalidArgument,
+            FilterError::InvalidFilter { .. } => ErrorCodes::InvalidArgument,
         }
     }
 }
 
+/// Human-readable name of a `MetadataValue`'s variant, used to report type mismatches without
+/// leaking the value itself into error messages.
+fn metadata_value_type_name(val: &MetadataValue) -> &'static str {
+    match val {
+        MetadataValue::Bool(_) => "bool",
+        MetadataValue::Int(_) => "int",
+        MetadataValue::Float(_) => "float",
+        MetadataValue::Str(_) => "string",
+    }
+}
+
 /// This sturct provides an abstraction over the materialized logs that is similar to the metadata segment
 pub(crate) struct MetadataLogReader<'me> {
     // This maps metadata keys to `BTreeMap`s, which further map values to offset ids
@@ -103,6 +251,9 @@ pub(crate) struct MetadataLogReader<'me> {
     compact_metadata: HashMap<String, BTreeMap<MetadataValue, RoaringBitmap>>,
     // This maps offset ids to documents, excluding deleted ones
     document: HashMap<u32, &'me str>,
+    // This maps offset ids to their full metadata map, excluding deleted ones. Used to verify
+    // candidates (e.g. geo distance) that can't be expressed as an index range lookup
+    metadata: HashMap<u32, HashMap<String, MetadataValue>>,
     // This contains all existing offset ids that are touched by the logs
     updated_offset_ids: RoaringBitmap,
     // This maps user ids to offset ids, excluding deleted ones
@@ -117,6 +268,7 @@ impl<'me> MetadataLogReader<'me> {
         let mut compact_metadata: HashMap<String, BTreeMap<MetadataValue, RoaringBitmap>> =
             HashMap::new();
         let mut document = HashMap::new();
+        let mut metadata = HashMap::new();
         let mut updated_offset_ids = RoaringBitmap::new();
         let mut user_id_to_offset_id = HashMap::new();
 
@@ -133,8 +285,8 @@ impl<'me> MetadataLogReader<'me> {
             ) {
                 let log = log.hydrate(record_segment_reader.as_ref()).await?;
                 user_id_to_offset_id.insert(log.get_user_id(), log.get_offset_id());
-                let log_metadata = log.merged_metadata();
-                for (key, val) in log_metadata.into_iter() {
+                let log_metadata: HashMap<String, MetadataValue> = log.merged_metadata();
+                for (key, val) in log_metadata.clone().into_iter() {
                     compact_metadata
                         .entry(key)
                         .or_default()
@@ -142,6 +294,7 @@ impl<'me> MetadataLogReader<'me> {
                         .or_default()
                         .insert(log.get_offset_id());
                 }
+                metadata.insert(log.get_offset_id(), log_metadata);
                 if let Some(doc) = log.merged_document_ref() {
                     document.insert(log.get_offset_id(), doc);
                 }
@@ -150,6 +303,7 @@ impl<'me> MetadataLogReader<'me> {
         Ok(Self {
             compact_metadata,
             document,
+            metadata,
             updated_offset_ids,
             user_id_to_offset_id,
         })
@@ -161,6 +315,18 @@ impl<'me> MetadataLogReader<'me> {
         op: &PrimitiveOperator,
     ) -> Result<RoaringBitmap, FilterError> {
         if let Some(metadata_value_to_offset_ids) = self.compact_metadata.get(key) {
+            if !metadata_value_to_offset_ids
+                .keys()
+                .any(|stored| std::mem::discriminant(stored) == std::mem::discriminant(val))
+            {
+                return Err(FilterError::InvalidFilter {
+                    key: key.to_string(),
+                    reason: format!(
+                        "key is only ever stored as a different type than {}",
+                        metadata_value_type_name(val)
+                    ),
+                });
+            }
             let bounds = match op {
                 PrimitiveOperator::Equal => (Bound::Included(val), Bound::Included(val)),
                 PrimitiveOperator::GreaterThan => (Bound::Excluded(val), Bound::Unbounded),
@@ -186,6 +352,101 @@ impl<'me> MetadataLogReader<'me> {
             .filter_map(|id| self.user_id_to_offset_id.get(id))
             .collect()
     }
+
+    /// All distinct values of `key` recorded in the materialized log, paired with the offset ids
+    /// holding each value. Used by `FacetDistributionOperator` to build per-value counts.
+    pub(crate) fn facet_values(&self, key: &str) -> Vec<(MetadataValue, RoaringBitmap)> {
+        self.compact_metadata
+            .get(key)
+            .map(|values| {
+                values
+                    .iter()
+                    .map(|(value, ids)| (value.clone(), ids.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// All offset ids the materialized log holds metadata for, regardless of `key`. Used by
+    /// `OrderByOperator` to resolve which offsets are missing a given sort key.
+    pub(crate) fn all_offset_ids(&self) -> RoaringBitmap {
+        self.metadata.keys().copied().collect()
+    }
+}
+
+/// The ngram size the full text index is built with. Candidate ngrams generated here must match
+/// this so the alternation regex below can reuse `FullTextIndexReader::match_literal_expression`.
+const FUZZY_NGRAM_SIZE: usize = 3;
+
+/// Returns true if `word` is within `max_edits` Levenshtein edits of `term`.
+///
+/// This runs the classic bounded edit-distance DP, which is the row-by-row realization of a
+/// Levenshtein automaton: row `i` holds, for every `(position-in-term, accumulated-edits)` state
+/// reachable after consuming `i` characters of `word`, the minimal edit count to reach that
+/// state, pruning (via `max_edits + 1` as a sentinel) any state whose edit count exceeds `k`.
+fn within_edit_distance(term: &str, max_edits: u8, word: &str) -> bool {
+    let term: Vec<char> = term.chars().collect();
+    let word: Vec<char> = word.chars().collect();
+    let k = max_edits as usize;
+    if term.len().abs_diff(word.len()) > k {
+        return false;
+    }
+    let mut prev_row: Vec<usize> = (0..=term.len()).collect();
+    for (i, &wc) in word.iter().enumerate() {
+        let mut curr_row = vec![i + 1; term.len() + 1];
+        for (j, &tc) in term.iter().enumerate() {
+            let substitution_cost = if tc == wc { 0 } else { 1 };
+            curr_row[j + 1] = (prev_row[j] + substitution_cost) // substitute (or match)
+                .min(prev_row[j + 1] + 1) // delete from word
+                .min(curr_row[j] + 1); // insert into word
+        }
+        prev_row = curr_row;
+    }
+    prev_row[term.len()] <= k
+}
+
+/// Splits document text into candidate tokens the way `within_edit_distance` expects them, i.e.
+/// contiguous runs of alphanumeric characters.
+fn tokenize_for_fuzzy_match(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+}
+
+/// Escapes the regex metacharacters in a literal ngram so it can be safely embedded in the
+/// alternation built by `fuzzy_candidate_literal_expr`.
+fn escape_regex_literal(literal: &str) -> String {
+    let mut escaped = String::with_capacity(literal.len());
+    for c in literal.chars() {
+        if !c.is_alphanumeric() {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Builds an over-approximating regex alternation of `term`'s ngrams, so we can reuse the
+/// existing ngram posting-list machinery (`FullTextIndexReader::match_literal_expression`) to
+/// gather candidates: of `term`'s `term.len() - FUZZY_NGRAM_SIZE + 1` ngrams, up to
+/// `k * FUZZY_NGRAM_SIZE` of them can be destroyed by `k` edits, so a word within edit distance
+/// `k` of `term` is only guaranteed to share an ngram with it when
+/// `term.len() >= (k + 1) * FUZZY_NGRAM_SIZE`, leaving at least one surviving ngram; the union of
+/// the matched ngrams' postings is then a safe (but not necessarily tight) superset of the true
+/// matches.
+fn fuzzy_candidate_literal_expr(term: &str, max_edits: u8) -> Option<LiteralExpr> {
+    let chars: Vec<char> = term.chars().collect();
+    if chars.len() < (max_edits as usize + 1) * FUZZY_NGRAM_SIZE {
+        // Too short relative to the edit budget for ngram sharing to be guaranteed: fall back to
+        // a full scan by returning `None`.
+        return None;
+    }
+    let alternation = chars
+        .windows(FUZZY_NGRAM_SIZE)
+        .map(|ngram| escape_regex_literal(&ngram.iter().collect::<String>()))
+        .collect::<Vec<_>>()
+        .join("|");
+    let chroma_regex = ChromaRegex::try_from(alternation).ok()?;
+    Some(LiteralExpr::from(chroma_regex.hir().clone()))
 }
 
 pub(crate) enum MetadataProvider<'me> {
@@ -296,6 +557,180 @@ impl<'me> MetadataProvider<'me> {
         }
     }
 
+    /// Finds documents whose text begins with `prefix`, without paying for a full regex compile.
+    pub(crate) async fn filter_by_document_prefix(
+        &self,
+        prefix: &str,
+    ) -> Result<RoaringBitmap, FilterError> {
+        match self {
+            MetadataProvider::CompactData(metadata_segment_reader, record_segment_reader) => {
+                if let (Some(fti_reader), Some(rec_reader)) = (
+                    metadata_segment_reader.full_text_index_reader.as_ref(),
+                    record_segment_reader,
+                ) {
+                    // Anchor the prefix as a regex so we reuse the ngram literal-matching
+                    // machinery the same way `filter_by_document_regex` does.
+                    let anchored_pattern = format!("^{}", escape_regex_literal(prefix));
+                    let chroma_regex = ChromaRegex::try_from(anchored_pattern)?;
+                    let literal_expr = LiteralExpr::from(chroma_regex.hir().clone());
+                    let approximate_matching_offset_ids = fti_reader
+                        .match_literal_expression(&literal_expr)
+                        .await
+                        .map_err(MetadataIndexError::from)?;
+
+                    let mut exact_matching_offset_ids = RoaringBitmap::new();
+                    match approximate_matching_offset_ids {
+                        // Perform point lookup for potential matching documents if there are not
+                        // too many of them, mirroring `filter_by_document_regex`
+                        Some(offset_ids)
+                            if offset_ids.len() < rec_reader.count().await? as u64 / 10 =>
+                        {
+                            for id in offset_ids {
+                                if rec_reader.get_data_for_offset_id(id).await?.is_some_and(
+                                    |rec| rec.document.is_some_and(|doc| doc.starts_with(prefix)),
+                                ) {
+                                    exact_matching_offset_ids.insert(id);
+                                }
+                            }
+                        }
+                        // Perform range scan of all documents
+                        candidate_offsets => {
+                            for (offset, record) in rec_reader
+                                .get_data_stream(..)
+                                .await
+                                .try_collect::<Vec<_>>()
+                                .await?
+                            {
+                                if (candidate_offsets.is_none()
+                                    || candidate_offsets
+                                        .as_ref()
+                                        .is_some_and(|offsets| offsets.contains(offset)))
+                                    && record.document.is_some_and(|doc| doc.starts_with(prefix))
+                                {
+                                    exact_matching_offset_ids.insert(offset);
+                                }
+                            }
+                        }
+                    }
+
+                    Ok(exact_matching_offset_ids)
+                } else {
+                    Ok(RoaringBitmap::new())
+                }
+            }
+            MetadataProvider::Log(metadata_log_reader) => Ok(metadata_log_reader
+                .document
+                .iter()
+                .filter_map(|(offset_id, document)| {
+                    document.starts_with(prefix).then_some(offset_id)
+                })
+                .collect()),
+        }
+    }
+
+    /// Finds documents containing a token within `max_edits` Levenshtein edits of `query`,
+    /// tolerating misspellings the exact `Contains`/`Regex` operators can't.
+    pub(crate) async fn filter_by_document_fuzzy(
+        &self,
+        query: &str,
+        max_edits: u8,
+    ) -> Result<RoaringBitmap, FilterError> {
+        match self {
+            MetadataProvider::CompactData(metadata_segment_reader, record_segment_reader) => {
+                if let (Some(fti_reader), Some(rec_reader)) = (
+                    metadata_segment_reader.full_text_index_reader.as_ref(),
+                    record_segment_reader,
+                ) {
+                    let candidate_offset_ids = match fuzzy_candidate_literal_expr(query, max_edits)
+                    {
+                        Some(literal_expr) => fti_reader
+                            .match_literal_expression(&literal_expr)
+                            .await
+                            .map_err(MetadataIndexError::from)?,
+                        None => None,
+                    };
+
+                    let mut matching_offset_ids = RoaringBitmap::new();
+                    match candidate_offset_ids {
+                        // Perform point lookup for potential matching documents if there are not
+                        // too many of them, mirroring `filter_by_document_regex`
+                        Some(offset_ids)
+                            if offset_ids.len() < rec_reader.count().await? as u64 / 10 =>
+                        {
+                            for id in offset_ids {
+                                if rec_reader.get_data_for_offset_id(id).await?.is_some_and(
+                                    |rec| {
+                                        rec.document.is_some_and(|doc| {
+                                            tokenize_for_fuzzy_match(doc)
+                                                .any(|tok| within_edit_distance(query, max_edits, tok))
+                                        })
+                                    },
+                                ) {
+                                    matching_offset_ids.insert(id);
+                                }
+                            }
+                        }
+                        // Perform range scan of all documents
+                        candidate_offsets => {
+                            for (offset, record) in rec_reader
+                                .get_data_stream(..)
+                                .await
+                                .try_collect::<Vec<_>>()
+                                .await?
+                            {
+                                if (candidate_offsets.is_none()
+                                    || candidate_offsets
+                                        .as_ref()
+                                        .is_some_and(|offsets| offsets.contains(offset)))
+                                    && record.document.is_some_and(|doc| {
+                                        tokenize_for_fuzzy_match(doc)
+                                            .any(|tok| within_edit_distance(query, max_edits, tok))
+                                    })
+                                {
+                                    matching_offset_ids.insert(offset);
+                                }
+                            }
+                        }
+                    }
+
+                    Ok(matching_offset_ids)
+                } else {
+                    Ok(RoaringBitmap::new())
+                }
+            }
+            MetadataProvider::Log(metadata_log_reader) => Ok(metadata_log_reader
+                .document
+                .iter()
+                .filter_map(|(offset_id, document)| {
+                    tokenize_for_fuzzy_match(document)
+                        .any(|tok| within_edit_distance(query, max_edits, tok))
+                        .then_some(offset_id)
+                })
+                .collect()),
+        }
+    }
+
+    /// Evaluates a primitive comparison against each of `candidates` directly, without touching
+    /// the metadata index. Used by the `PerCandidate` strategy once the universe is already
+    /// narrow enough that this beats walking the whole posting list.
+    pub(crate) async fn filter_by_metadata_per_candidate(
+        &self,
+        key: &str,
+        val: &MetadataValue,
+        op: &PrimitiveOperator,
+        candidates: &RoaringBitmap,
+    ) -> Result<RoaringBitmap, FilterError> {
+        let mut matching_offset_ids = RoaringBitmap::new();
+        for offset_id in candidates {
+            if let Some(stored) = self.get_metadata_value(key, offset_id).await? {
+                if compare_primitive(&stored, op, val) {
+                    matching_offset_ids.insert(offset_id);
+                }
+            }
+        }
+        Ok(matching_offset_ids)
+    }
+
     pub(crate) async fn filter_by_metadata(
         &self,
         key: &str,
@@ -336,18 +771,324 @@ impl<'me> MetadataProvider<'me> {
                         ),
                     }
                 } else {
-                    Ok(RoaringBitmap::new())
+                    // No index of this value's type exists at all. If the key is actually stored
+                    // under a different type elsewhere in the segment, this filter can never
+                    // match anything and the caller deserves a clear error rather than silence.
+                    // `contains_key` below is assumed present on `chroma_segment`'s metadata index
+                    // reader types; that crate isn't part of this checkout, so it can't be
+                    // verified or added from this side — flagging per the review rather than
+                    // guessing at an unseen crate's API.
+                    let stored_under_different_type = [
+                        metadata_segment_reader
+                            .bool_metadata_index_reader
+                            .as_ref()
+                            .is_some_and(|r| r.contains_key(key)),
+                        metadata_segment_reader
+                            .u32_metadata_index_reader
+                            .as_ref()
+                            .is_some_and(|r| r.contains_key(key)),
+                        metadata_segment_reader
+                            .f32_metadata_index_reader
+                            .as_ref()
+                            .is_some_and(|r| r.contains_key(key)),
+                        metadata_segment_reader
+                            .string_metadata_index_reader
+                            .as_ref()
+                            .is_some_and(|r| r.contains_key(key)),
+                    ]
+                    .into_iter()
+                    .any(|present| present);
+
+                    if stored_under_different_type {
+                        Err(FilterError::InvalidFilter {
+                            key: key.to_string(),
+                            reason: format!(
+                                "key is only ever stored as a different type than {}",
+                                metadata_value_type_name(val)
+                            ),
+                        })
+                    } else {
+                        Ok(RoaringBitmap::new())
+                    }
                 }
             }
             MetadataProvider::Log(metadata_log_reader) => metadata_log_reader.get(key, val, op),
         }
     }
+
+    /// All distinct values of `key` across this provider's own universe, paired with the offset
+    /// ids holding each value. Unlike `filter_by_metadata`, this walks the whole posting list for
+    /// `key` rather than looking up a single value; used by `FacetDistributionOperator`.
+    pub(crate) async fn facet_values(
+        &self,
+        key: &str,
+    ) -> Result<Vec<(MetadataValue, RoaringBitmap)>, FilterError> {
+        match self {
+            // `contains_key`/`get_all` below are assumed present on `chroma_segment`'s metadata
+            // index reader types; that crate isn't part of this checkout, so neither method can
+            // be verified or added from this side — flagging per the review rather than
+            // guessing at an unseen crate's API.
+            MetadataProvider::CompactData(metadata_segment_reader, _) => {
+                // A key can be stored under more than one type, so every typed index is read.
+                let mut values = Vec::new();
+                if let Some(reader) = metadata_segment_reader.bool_metadata_index_reader.as_ref() {
+                    if reader.contains_key(key) {
+                        values.extend(
+                            reader
+                                .get_all(key)
+                                .await?
+                                .into_iter()
+                                .map(|(value, ids)| (MetadataValue::Bool(value), ids)),
+                        );
+                    }
+                }
+                if let Some(reader) = metadata_segment_reader.u32_metadata_index_reader.as_ref() {
+                    if reader.contains_key(key) {
+                        values.extend(
+                            reader
+                                .get_all(key)
+                                .await?
+                                .into_iter()
+                                .map(|(value, ids)| (MetadataValue::Int(value as i64), ids)),
+                        );
+                    }
+                }
+                if let Some(reader) = metadata_segment_reader.f32_metadata_index_reader.as_ref() {
+                    if reader.contains_key(key) {
+                        values.extend(
+                            reader
+                                .get_all(key)
+                                .await?
+                                .into_iter()
+                                .map(|(value, ids)| (MetadataValue::Float(value as f64), ids)),
+                        );
+                    }
+                }
+                if let Some(reader) = metadata_segment_reader
+                    .string_metadata_index_reader
+                    .as_ref()
+                {
+                    if reader.contains_key(key) {
+                        values.extend(
+                            reader
+                                .get_all(key)
+                                .await?
+                                .into_iter()
+                                .map(|(value, ids)| (MetadataValue::Str(value), ids)),
+                        );
+                    }
+                }
+                Ok(values)
+            }
+            MetadataProvider::Log(metadata_log_reader) => Ok(metadata_log_reader.facet_values(key)),
+        }
+    }
+
+    /// Offsets whose metadata value at `key` falls in `[lo, hi]`, used as a cheap pre-filter
+    /// before the exact haversine check in `filter_by_geo_radius`/`filter_by_geo_bounding_box`.
+    async fn filter_by_numeric_range(
+        &self,
+        key: &str,
+        lo: f64,
+        hi: f64,
+    ) -> Result<RoaringBitmap, FilterError> {
+        let ge_lo = self
+            .filter_by_numeric_comparison(key, &PrimitiveOperator::GreaterThanOrEqual, lo)
+            .await?;
+        let le_hi = self
+            .filter_by_numeric_comparison(key, &PrimitiveOperator::LessThanOrEqual, hi)
+            .await?;
+        Ok(ge_lo & le_hi)
+    }
+
+    /// `filter_by_metadata(key, op, value)`, but tries both the `Float` and `Int` metadata index
+    /// when `value` is queried as a `Float` and the key turns out to be stored as `Int` instead
+    /// (e.g. whole-degree coordinates). `get_numeric_metadata`'s exact-verification step already
+    /// treats `Int`/`Float` identically via `metadata_value_as_f64`, so the pre-filter needs to as
+    /// well, or a geo query over `Int`-typed coordinates would spuriously fail with
+    /// `InvalidFilter` instead of finding matches. The bound is rounded towards the integers that
+    /// satisfy the real-valued comparison, since the int index only supports integer ordering.
+    async fn filter_by_numeric_comparison(
+        &self,
+        key: &str,
+        op: &PrimitiveOperator,
+        value: f64,
+    ) -> Result<RoaringBitmap, FilterError> {
+        match self
+            .filter_by_metadata(key, &MetadataValue::Float(value), op)
+            .await
+        {
+            Err(FilterError::InvalidFilter { .. }) => {
+                let int_value = match op {
+                    PrimitiveOperator::GreaterThanOrEqual => value.ceil() as i64,
+                    PrimitiveOperator::LessThanOrEqual => value.floor() as i64,
+                    _ => unreachable!(
+                        "filter_by_numeric_range only ever compares with >= or <="
+                    ),
+                };
+                self.filter_by_metadata(key, &MetadataValue::Int(int_value), op)
+                    .await
+            }
+            other => other,
+        }
+    }
+
+    /// The metadata value stored at `key` for `offset_id`, used to evaluate a leaf clause against
+    /// a single candidate directly instead of walking the metadata index's posting lists.
+    pub(crate) async fn get_metadata_value(
+        &self,
+        key: &str,
+        offset_id: u32,
+    ) -> Result<Option<MetadataValue>, FilterError> {
+        match self {
+            MetadataProvider::CompactData(_, record_segment_reader) => {
+                if let Some(reader) = record_segment_reader.as_ref() {
+                    if let Some(record) = reader.get_data_for_offset_id(offset_id).await? {
+                        return Ok(record.metadata.and_then(|m| m.get(key).cloned()));
+                    }
+                }
+                Ok(None)
+            }
+            MetadataProvider::Log(metadata_log_reader) => Ok(metadata_log_reader
+                .metadata
+                .get(&offset_id)
+                .and_then(|m| m.get(key).cloned())),
+        }
+    }
+
+    /// The numeric value stored at `key` for `offset_id`, used to verify geo candidates exactly.
+    async fn get_numeric_metadata(
+        &self,
+        key: &str,
+        offset_id: u32,
+    ) -> Result<Option<f64>, FilterError> {
+        Ok(self
+            .get_metadata_value(key, offset_id)
+            .await?
+            .as_ref()
+            .and_then(metadata_value_as_f64))
+    }
+
+    /// Offsets within `meters` of `center`, refined from a cheap bounding-box pre-filter on
+    /// `lat_key`/`lon_key` by an exact haversine distance check.
+    pub(crate) async fn filter_by_geo_radius(
+        &self,
+        lat_key: &str,
+        lon_key: &str,
+        center: [f64; 2],
+        meters: f64,
+    ) -> Result<RoaringBitmap, FilterError> {
+        let center_lat = center[0].clamp(-90.0, 90.0);
+        let center_lon = center[1];
+        let lat_delta = meters / METERS_PER_DEGREE_LAT;
+        let lon_delta = meters / (METERS_PER_DEGREE_LAT * center_lat.to_radians().cos().max(1e-9));
+
+        let candidates = self
+            .filter_by_numeric_range(lat_key, center_lat - lat_delta, center_lat + lat_delta)
+            .await?
+            & self
+                .filter_by_numeric_range(lon_key, center_lon - lon_delta, center_lon + lon_delta)
+                .await?;
+
+        let mut matching_offset_ids = RoaringBitmap::new();
+        for offset_id in candidates {
+            if let (Some(lat), Some(lon)) = (
+                self.get_numeric_metadata(lat_key, offset_id).await?,
+                self.get_numeric_metadata(lon_key, offset_id).await?,
+            ) {
+                if haversine_distance_meters((center_lat, center_lon), (lat, lon)) <= meters {
+                    matching_offset_ids.insert(offset_id);
+                }
+            }
+        }
+        Ok(matching_offset_ids)
+    }
+
+    /// Offsets falling within the axis-aligned lat/lon box, handling antimeridian wraparound by
+    /// splitting the longitude range into two when `top_left.lon > bottom_right.lon`.
+    pub(crate) async fn filter_by_geo_bounding_box(
+        &self,
+        lat_key: &str,
+        lon_key: &str,
+        top_left: [f64; 2],
+        bottom_right: [f64; 2],
+    ) -> Result<RoaringBitmap, FilterError> {
+        let lat_range = self
+            .filter_by_numeric_range(
+                lat_key,
+                bottom_right[0].clamp(-90.0, 90.0),
+                top_left[0].clamp(-90.0, 90.0),
+            )
+            .await?;
+
+        let lon_range = if top_left[1] > bottom_right[1] {
+            self.filter_by_numeric_range(lon_key, top_left[1], 180.0)
+                .await?
+                | self
+                    .filter_by_numeric_range(lon_key, -180.0, bottom_right[1])
+                    .await?
+        } else {
+            self.filter_by_numeric_range(lon_key, top_left[1], bottom_right[1])
+                .await?
+        };
+
+        Ok(lat_range & lon_range)
+    }
+}
+
+/// Mean earth radius in meters, used by the haversine great-circle distance calculation.
+const EARTH_RADIUS_METERS: f64 = 6_371_000.0;
+/// Meters per degree of latitude, used to turn a radius/bounding box into a cheap lat/lon range
+/// pre-filter before the exact haversine check.
+const METERS_PER_DEGREE_LAT: f64 = 111_320.0;
+
+/// Evaluates a single stored metadata value against a query value directly, used by the
+/// `PerCandidate` strategy in place of a metadata index posting-list lookup. Values of different
+/// types never match, mirroring the index path, which only ever stores one type per reader.
+fn compare_primitive(stored: &MetadataValue, op: &PrimitiveOperator, query: &MetadataValue) -> bool {
+    if std::mem::discriminant(stored) != std::mem::discriminant(query) {
+        return false;
+    }
+    match op {
+        PrimitiveOperator::Equal => stored == query,
+        PrimitiveOperator::GreaterThan => stored > query,
+        PrimitiveOperator::GreaterThanOrEqual => stored >= query,
+        PrimitiveOperator::LessThan => stored < query,
+        PrimitiveOperator::LessThanOrEqual => stored <= query,
+        PrimitiveOperator::NotEqual => {
+            unreachable!("Inequality filter should be handled above the metadata provider level")
+        }
+    }
+}
+
+pub(crate) fn metadata_value_as_f64(val: &MetadataValue) -> Option<f64> {
+    match val {
+        MetadataValue::Int(i) => Some(*i as f64),
+        MetadataValue::Float(f) => Some(*f),
+        _ => None,
+    }
+}
+
+/// Great-circle distance between two (lat, lon) points in degrees, in meters.
+fn haversine_distance_meters(a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (lat1, lon1) = a;
+    let (lat2, lon2) = b;
+    let d_phi = (lat2 - lat1).to_radians();
+    let d_lambda = (lon2 - lon1).to_radians();
+    let phi1 = lat1.to_radians();
+    let phi2 = lat2.to_radians();
+    let sin_d_phi_half = (d_phi / 2.0).sin();
+    let sin_d_lambda_half = (d_lambda / 2.0).sin();
+    let a = sin_d_phi_half * sin_d_phi_half
+        + phi1.cos() * phi2.cos() * sin_d_lambda_half * sin_d_lambda_half;
+    2.0 * EARTH_RADIUS_METERS * a.sqrt().asin()
 }
 
 pub(crate) trait RoaringMetadataFilter<'me> {
     async fn eval(
         &'me self,
         metadata_provider: &MetadataProvider<'me>,
+        ctx: EvalContext<'_>,
     ) -> Result<SignedRoaringBitmap, FilterError>;
 }
 
@@ -355,17 +1096,70 @@ impl<'me> RoaringMetadataFilter<'me> for Where {
     async fn eval(
         &'me self,
         metadata_provider: &MetadataProvider<'me>,
+        ctx: EvalContext<'_>,
     ) -> Result<SignedRoaringBitmap, FilterError> {
-        match self {
-            Where::Metadata(direct_comparison) => direct_comparison.eval(metadata_provider).await,
+        let (cache_key, cache_debug) = hash_where(self);
+        if let Some((debug, cached)) = ctx.cache.clause_results.borrow().get(&cache_key) {
+            if *debug == cache_debug {
+                return Ok(cached.clone());
+            }
+        }
+
+        let result = match self {
+            Where::Metadata(direct_comparison) => {
+                direct_comparison.eval(metadata_provider, ctx).await
+            }
             Where::Document(direct_document_comparison) => {
-                direct_document_comparison.eval(metadata_provider).await
+                direct_document_comparison.eval(metadata_provider, ctx).await
             }
             Where::Composite(where_children) => {
                 // Box::pin is required to avoid infinite size future when recurse in async
-                Box::pin(where_children.eval(metadata_provider)).await
+                Box::pin(where_children.eval(metadata_provider, ctx)).await
             }
-        }
+            // `Where::Geo` and `GeoExpression` itself (below) need to be declared in `Where`'s
+            // and `chroma_types`'s defining module, which isn't part of this checkout (see the
+            // note above the `chroma_types` import) — this match arm only compiles once that
+            // variant exists there.
+            Where::Geo(geo_expression) => geo_expression.eval(metadata_provider, ctx).await,
+        }?;
+
+        ctx.cache
+            .clause_results
+            .borrow_mut()
+            .insert(cache_key, (cache_debug, result.clone()));
+        Ok(result)
+    }
+}
+
+impl<'me> RoaringMetadataFilter<'me> for GeoExpression {
+    async fn eval(
+        &'me self,
+        metadata_provider: &MetadataProvider<'me>,
+        _ctx: EvalContext<'_>,
+    ) -> Result<SignedRoaringBitmap, FilterError> {
+        let result = match self {
+            GeoExpression::Radius {
+                lat_key,
+                lon_key,
+                center,
+                meters,
+            } => {
+                metadata_provider
+                    .filter_by_geo_radius(lat_key, lon_key, *center, *meters)
+                    .await?
+            }
+            GeoExpression::BoundingBox {
+                lat_key,
+                lon_key,
+                top_left,
+                bottom_right,
+            } => {
+                metadata_provider
+                    .filter_by_geo_bounding_box(lat_key, lon_key, *top_left, *bottom_right)
+                    .await?
+            }
+        };
+        Ok(SignedRoaringBitmap::Include(result))
     }
 }
 
@@ -373,29 +1167,64 @@ impl<'me> RoaringMetadataFilter<'me> for MetadataExpression {
     async fn eval(
         &'me self,
         metadata_provider: &MetadataProvider<'me>,
+        ctx: EvalContext<'_>,
     ) -> Result<SignedRoaringBitmap, FilterError> {
         let result = match &self.comparison {
             MetadataComparison::Primitive(primitive_operator, metadata_value) => {
                 match primitive_operator {
                     // We convert the inequality check in to an equality check, and then negate the result
-                    PrimitiveOperator::NotEqual => SignedRoaringBitmap::Exclude(
-                        metadata_provider
-                            .filter_by_metadata(
-                                &self.key,
-                                metadata_value,
-                                &PrimitiveOperator::Equal,
-                            )
-                            .await?,
-                    ),
+                    PrimitiveOperator::NotEqual => {
+                        SignedRoaringBitmap::Exclude(match (ctx.strategy, ctx.candidates) {
+                            (FilterStrategy::PerCandidate, Some(candidates)) => {
+                                metadata_provider
+                                    .filter_by_metadata_per_candidate(
+                                        &self.key,
+                                        metadata_value,
+                                        &PrimitiveOperator::Equal,
+                                        candidates,
+                                    )
+                                    .await?
+                            }
+                            _ => {
+                                cached_filter_by_metadata(
+                                    metadata_provider,
+                                    ctx.cache,
+                                    &self.key,
+                                    metadata_value,
+                                    &PrimitiveOperator::Equal,
+                                )
+                                .await?
+                            }
+                        })
+                    }
                     PrimitiveOperator::Equal
                     | PrimitiveOperator::GreaterThan
                     | PrimitiveOperator::GreaterThanOrEqual
                     | PrimitiveOperator::LessThan
-                    | PrimitiveOperator::LessThanOrEqual => SignedRoaringBitmap::Include(
-                        metadata_provider
-                            .filter_by_metadata(&self.key, metadata_value, primitive_operator)
-                            .await?,
-                    ),
+                    | PrimitiveOperator::LessThanOrEqual => {
+                        SignedRoaringBitmap::Include(match (ctx.strategy, ctx.candidates) {
+                            (FilterStrategy::PerCandidate, Some(candidates)) => {
+                                metadata_provider
+                                    .filter_by_metadata_per_candidate(
+                                        &self.key,
+                                        metadata_value,
+                                        primitive_operator,
+                                        candidates,
+                                    )
+                                    .await?
+                            }
+                            _ => {
+                                cached_filter_by_metadata(
+                                    metadata_provider,
+                                    ctx.cache,
+                                    &self.key,
+                                    metadata_value,
+                                    primitive_operator,
+                                )
+                                .await?
+                            }
+                        })
+                    }
                 }
             }
             MetadataComparison::Set(set_operator, metadata_set_value) => {
@@ -415,9 +1244,28 @@ impl<'me> RoaringMetadataFilter<'me> for MetadataExpression {
                 };
                 let mut child_evaluations = Vec::with_capacity(child_values.len());
                 for value in child_values {
-                    let eval = metadata_provider
-                        .filter_by_metadata(&self.key, &value, &PrimitiveOperator::Equal)
-                        .await?;
+                    let eval = match (ctx.strategy, ctx.candidates) {
+                        (FilterStrategy::PerCandidate, Some(candidates)) => {
+                            metadata_provider
+                                .filter_by_metadata_per_candidate(
+                                    &self.key,
+                                    &value,
+                                    &PrimitiveOperator::Equal,
+                                    candidates,
+                                )
+                                .await?
+                        }
+                        _ => {
+                            cached_filter_by_metadata(
+                                metadata_provider,
+                                ctx.cache,
+                                &self.key,
+                                &value,
+                                &PrimitiveOperator::Equal,
+                            )
+                            .await?
+                        }
+                    };
                     match set_operator {
                         SetOperator::In => {
                             child_evaluations.push(SignedRoaringBitmap::Include(eval))
@@ -445,6 +1293,9 @@ impl<'me> RoaringMetadataFilter<'me> for DocumentExpression {
     async fn eval(
         &'me self,
         metadata_provider: &MetadataProvider<'me>,
+        // Document operators already choose between a point-lookup and a range-scan
+        // internally, independent of the candidate-count threshold used for metadata.
+        _ctx: EvalContext<'_>,
     ) -> Result<SignedRoaringBitmap, FilterError> {
         match self.operator {
             DocumentOperator::Contains => Ok(SignedRoaringBitmap::Include(
@@ -467,6 +1318,34 @@ impl<'me> RoaringMetadataFilter<'me> for DocumentExpression {
                     .filter_by_document_regex(self.pattern.as_str())
                     .await?,
             )),
+            // `StartsWith`/`NotStartsWith` (and `FuzzyContains`/`NotFuzzyContains` below) need to
+            // be declared as variants of `DocumentOperator` in its defining module, which isn't
+            // part of this checkout (see the note above the `chroma_types` import) — this match
+            // only compiles once those variants exist there.
+            DocumentOperator::StartsWith => Ok(SignedRoaringBitmap::Include(
+                metadata_provider
+                    .filter_by_document_prefix(self.pattern.as_str())
+                    .await?,
+            )),
+            DocumentOperator::NotStartsWith => Ok(SignedRoaringBitmap::Exclude(
+                metadata_provider
+                    .filter_by_document_prefix(self.pattern.as_str())
+                    .await?,
+            )),
+            // `FuzzyContains`/`NotFuzzyContains` (the `{ max_edits }` payload included) need to be
+            // declared as variants of `DocumentOperator` in its defining module, which isn't part
+            // of this checkout (see the note above the `chroma_types` import) — this match only
+            // compiles once those variants exist there.
+            DocumentOperator::FuzzyContains { max_edits } => Ok(SignedRoaringBitmap::Include(
+                metadata_provider
+                    .filter_by_document_fuzzy(self.pattern.as_str(), max_edits)
+                    .await?,
+            )),
+            DocumentOperator::NotFuzzyContains { max_edits } => Ok(SignedRoaringBitmap::Exclude(
+                metadata_provider
+                    .filter_by_document_fuzzy(self.pattern.as_str(), max_edits)
+                    .await?,
+            )),
         }
     }
 }
@@ -475,10 +1354,11 @@ impl<'me> RoaringMetadataFilter<'me> for CompositeExpression {
     async fn eval(
         &'me self,
         metadata_provider: &MetadataProvider<'me>,
+        ctx: EvalContext<'_>,
     ) -> Result<SignedRoaringBitmap, FilterError> {
         let mut child_evaluations = Vec::new();
         for child in &self.children {
-            child_evaluations.push(child.eval(metadata_provider).await?);
+            child_evaluations.push(child.eval(metadata_provider, ctx).await?);
         }
         match self.operator {
             BooleanOperator::And => Ok(child_evaluations
@@ -569,9 +1449,21 @@ impl Operator<FilterInput, FilterOutput> for FilterOperator {
                 (SignedRoaringBitmap::full(), SignedRoaringBitmap::full())
             };
 
+        // Resolve the evaluation strategy once, before the candidate bitmaps are
+        // consumed by the `&` operators below.
+        let log_eval_cache = EvalCache::default();
+        let compact_eval_cache = EvalCache::default();
+        let log_eval_ctx =
+            EvalContext::new(self.strategy, &user_allowed_log_offset_ids, &log_eval_cache);
+        let compact_eval_ctx = EvalContext::new(
+            self.strategy,
+            &user_allowed_compact_offset_ids,
+            &compact_eval_cache,
+        );
+
         // Filter the offset ids in the log if the where clause is provided
         let log_offset_ids = if let Some(clause) = self.where_clause.as_ref() {
-            clause.eval(&log_metadata_provider).await? & user_allowed_log_offset_ids
+            clause.eval(&log_metadata_provider, log_eval_ctx).await? & user_allowed_log_offset_ids
         } else {
             user_allowed_log_offset_ids
         };
@@ -579,7 +1471,7 @@ impl Operator<FilterInput, FilterOutput> for FilterOperator {
         // Filter the offset ids in the metadata segment if the where clause is provided
         // This always exclude all offsets that is present in the materialized log
         let compact_offset_ids = if let Some(clause) = self.where_clause.as_ref() {
-            clause.eval(&compact_metadata_provider).await?
+            clause.eval(&compact_metadata_provider, compact_eval_ctx).await?
                 & user_allowed_compact_offset_ids
                 & SignedRoaringBitmap::Exclude(metadata_log_reader.updated_offset_ids)
         } else {
@@ -607,7 +1499,10 @@ mod tests {
 
     use crate::execution::operators::filter::FilterOperator;
 
-    use super::FilterInput;
+    use super::{
+        EvalCache, EvalContext, FilterInput, FilterStrategy, MetadataLogReader, MetadataProvider,
+        RoaringMetadataFilter,
+    };
 
     /// The unit tests for `FilterOperator` uses the following test data
     /// It generates 120 log records, where the first 60 is compacted:
@@ -633,8 +1528,8 @@ mod tests {
         let filter_operator = FilterOperator {
             query_ids: None,
             where_clause: None,
+            strategy: FilterStrategy::Auto,
         };
-
         let filter_output = filter_operator
             .run(&filter_input)
             .await
@@ -654,8 +1549,8 @@ mod tests {
         let filter_operator = FilterOperator {
             query_ids: Some((0..30).map(int_as_id).collect()),
             where_clause: None,
+            strategy: FilterStrategy::Auto,
         };
-
         let filter_output = filter_operator
             .run(&filter_input)
             .await
@@ -683,8 +1578,8 @@ mod tests {
         let filter_operator = FilterOperator {
             query_ids: None,
             where_clause: Some(where_clause),
+            strategy: FilterStrategy::Auto,
         };
-
         let filter_output = filter_operator
             .run(&filter_input)
             .await
@@ -715,8 +1610,8 @@ mod tests {
         let filter_operator = FilterOperator {
             query_ids: None,
             where_clause: Some(where_clause),
+            strategy: FilterStrategy::Auto,
         };
-
         let filter_output = filter_operator
             .run(&filter_input)
             .await
@@ -752,8 +1647,8 @@ mod tests {
         let filter_operator = FilterOperator {
             query_ids: None,
             where_clause: Some(where_clause),
+            strategy: FilterStrategy::Auto,
         };
-
         let filter_output = filter_operator
             .run(&filter_input)
             .await
@@ -784,8 +1679,8 @@ mod tests {
         let filter_operator = FilterOperator {
             query_ids: None,
             where_clause: Some(where_clause),
+            strategy: FilterStrategy::Auto,
         };
-
         let filter_output = filter_operator
             .run(&filter_input)
             .await
@@ -821,8 +1716,8 @@ mod tests {
         let filter_operator = FilterOperator {
             query_ids: None,
             where_clause: Some(where_clause),
+            strategy: FilterStrategy::Auto,
         };
-
         let filter_output = filter_operator
             .run(&filter_input)
             .await
@@ -850,8 +1745,8 @@ mod tests {
         let filter_operator = FilterOperator {
             query_ids: None,
             where_clause: Some(where_clause),
+            strategy: FilterStrategy::Auto,
         };
-
         let filter_output = filter_operator
             .run(&filter_input)
             .await
@@ -879,8 +1774,8 @@ mod tests {
         let filter_operator = FilterOperator {
             query_ids: None,
             where_clause: Some(where_clause),
+            strategy: FilterStrategy::Auto,
         };
-
         let filter_output = filter_operator
             .run(&filter_input)
             .await
@@ -929,8 +1824,8 @@ mod tests {
         let filter_operator = FilterOperator {
             query_ids: None,
             where_clause: Some(where_clause),
+            strategy: FilterStrategy::Auto,
         };
-
         let filter_output = filter_operator
             .run(&filter_input)
             .await
@@ -974,8 +1869,8 @@ mod tests {
         let filter_operator = FilterOperator {
             query_ids: None,
             where_clause: Some(where_clause),
+            strategy: FilterStrategy::Auto,
         };
-
         let filter_output = filter_operator
             .run(&filter_input)
             .await
@@ -1030,8 +1925,8 @@ mod tests {
         let filter_operator = FilterOperator {
             query_ids: Some((0..96).map(int_as_id).collect()),
             where_clause: Some(where_clause),
+            strategy: FilterStrategy::Auto,
         };
-
         let filter_output = filter_operator
             .run(&filter_input)
             .await
@@ -1050,4 +1945,189 @@ mod tests {
             SignedRoaringBitmap::Include((21..=50).filter(|offset| offset % 5 != 0).collect())
         );
     }
+
+    #[tokio::test]
+    async fn test_per_candidate_strategy_matches_index_scan() {
+        let filter_input = setup_filter_input().await;
+
+        fn where_clause() -> Where {
+            Where::Metadata(MetadataExpression {
+                key: "is_even".to_string(),
+                comparison: MetadataComparison::Primitive(
+                    PrimitiveOperator::Equal,
+                    MetadataValue::Bool(true),
+                ),
+            })
+        }
+
+        // `query_ids` narrows the universe well below `CANDIDATES_THRESHOLD`, so
+        // `FilterStrategy::Auto` should resolve to per-candidate evaluation here.
+        let auto_operator = FilterOperator {
+            query_ids: Some((0..30).map(int_as_id).collect()),
+            where_clause: Some(where_clause()),
+            strategy: FilterStrategy::Auto,
+        };
+        let auto_output = auto_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+
+        let per_candidate_operator = FilterOperator {
+            query_ids: Some((0..30).map(int_as_id).collect()),
+            where_clause: Some(where_clause()),
+            strategy: FilterStrategy::PerCandidate,
+        };
+        let per_candidate_output = per_candidate_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+
+        let index_scan_operator = FilterOperator {
+            query_ids: Some((0..30).map(int_as_id).collect()),
+            where_clause: Some(where_clause()),
+            strategy: FilterStrategy::IndexScan,
+        };
+        let index_scan_output = index_scan_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+
+        assert_eq!(auto_output.log_offset_ids, per_candidate_output.log_offset_ids);
+        assert_eq!(
+            auto_output.compact_offset_ids,
+            per_candidate_output.compact_offset_ids
+        );
+        assert_eq!(
+            index_scan_output.log_offset_ids,
+            per_candidate_output.log_offset_ids
+        );
+        assert_eq!(
+            index_scan_output.compact_offset_ids,
+            per_candidate_output.compact_offset_ids
+        );
+        assert_eq!(
+            per_candidate_output.compact_offset_ids,
+            SignedRoaringBitmap::Include((21..30).filter(|offset| offset % 2 == 0).collect())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_per_candidate_strategy_matches_index_scan_for_set_comparison() {
+        let filter_input = setup_filter_input().await;
+
+        fn where_clause() -> Where {
+            Where::Metadata(MetadataExpression {
+                key: "modulo_3".to_string(),
+                comparison: MetadataComparison::Set(
+                    SetOperator::In,
+                    MetadataSetValue::Int(vec![1, 2]),
+                ),
+            })
+        }
+
+        // `query_ids` narrows the universe well below `CANDIDATES_THRESHOLD`, so
+        // `FilterStrategy::Auto` should resolve to per-candidate evaluation here, exercising
+        // the same per-value path `MetadataComparison::Set` takes as `Primitive` comparisons.
+        let auto_operator = FilterOperator {
+            query_ids: Some((0..30).map(int_as_id).collect()),
+            where_clause: Some(where_clause()),
+            strategy: FilterStrategy::Auto,
+        };
+        let auto_output = auto_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+
+        let per_candidate_operator = FilterOperator {
+            query_ids: Some((0..30).map(int_as_id).collect()),
+            where_clause: Some(where_clause()),
+            strategy: FilterStrategy::PerCandidate,
+        };
+        let per_candidate_output = per_candidate_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+
+        let index_scan_operator = FilterOperator {
+            query_ids: Some((0..30).map(int_as_id).collect()),
+            where_clause: Some(where_clause()),
+            strategy: FilterStrategy::IndexScan,
+        };
+        let index_scan_output = index_scan_operator
+            .run(&filter_input)
+            .await
+            .expect("FilterOperator should not fail");
+
+        assert_eq!(auto_output.log_offset_ids, per_candidate_output.log_offset_ids);
+        assert_eq!(
+            auto_output.compact_offset_ids,
+            per_candidate_output.compact_offset_ids
+        );
+        assert_eq!(
+            index_scan_output.log_offset_ids,
+            per_candidate_output.log_offset_ids
+        );
+        assert_eq!(
+            index_scan_output.compact_offset_ids,
+            per_candidate_output.compact_offset_ids
+        );
+    }
+
+    #[tokio::test]
+    async fn test_eval_cache_memoizes_repeated_leaf() {
+        let filter_input = setup_filter_input().await;
+
+        let record_segment_reader = super::RecordSegmentReader::from_segment(
+            &filter_input.record_segment,
+            &filter_input.blockfile_provider,
+        )
+        .await
+        .ok();
+        let materialized_logs =
+            super::materialize_logs(&record_segment_reader, filter_input.logs.clone(), None)
+                .await
+                .expect("materialize_logs should not fail");
+        let metadata_log_reader =
+            MetadataLogReader::create(&materialized_logs, &record_segment_reader)
+                .await
+                .expect("MetadataLogReader::create should not fail");
+        let log_metadata_provider = MetadataProvider::Log(&metadata_log_reader);
+
+        fn leaf() -> Where {
+            Where::Metadata(MetadataExpression {
+                key: "is_even".to_string(),
+                comparison: MetadataComparison::Primitive(
+                    PrimitiveOperator::Equal,
+                    MetadataValue::Bool(true),
+                ),
+            })
+        }
+
+        // The same leaf, constructed independently three times, appears in both branches of
+        // the `Or` below. It should only ever be evaluated once against the provider.
+        let where_clause = Where::Composite(CompositeExpression {
+            operator: BooleanOperator::Or,
+            children: vec![
+                leaf(),
+                Where::Composite(CompositeExpression {
+                    operator: BooleanOperator::And,
+                    children: vec![leaf(), leaf()],
+                }),
+            ],
+        });
+
+        let cache = EvalCache::default();
+        let allowed = SignedRoaringBitmap::full();
+        let ctx = EvalContext::new(FilterStrategy::IndexScan, &allowed, &cache);
+        let result = where_clause
+            .eval(&log_metadata_provider, ctx)
+            .await
+            .expect("eval should not fail");
+
+        assert_eq!(cache.posting_list_miss_count(), 1);
+        assert_eq!(
+            result,
+            SignedRoaringBitmap::Include((51..=100).filter(|offset| offset % 2 == 0).collect())
+        );
+    }
 }
@@ -0,0 +1,354 @@
+use async_trait::async_trait;
+use chroma_blockstore::provider::BlockfileProvider;
+use chroma_error::{ChromaError, ErrorCodes};
+use chroma_segment::{
+    blockfile_metadata::MetadataSegmentReader,
+    blockfile_record::{RecordSegmentReader, RecordSegmentReaderCreationError},
+    types::materialize_logs,
+};
+use chroma_system::Operator;
+use chroma_types::{Chunk, LogRecord, MetadataValue, Segment, SignedRoaringBitmap};
+use futures::TryStreamExt;
+use roaring::RoaringBitmap;
+use thiserror::Error;
+use tracing::{Instrument, Span};
+
+use super::filter::{FilterError, FilterOutput, MetadataLogReader, MetadataProvider};
+
+/// Sort direction for `OrderByOperator`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortDirection {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+/// The `OrderByOperator` retrieves the offset ids allowed by a prior `FilterOperator` run, sorted
+/// by a metadata key. Every distinct value of the key on both sides is gathered, together with
+/// its allowed offset ids, and all of them are sorted before any id is emitted; only the emission
+/// of ids stops once `offset + limit` of them are collected.
+///
+/// # Parameters
+/// - `key`: The metadata key to sort by
+/// - `direction`: Ascending or descending
+/// - `limit`: The maximum number of offset ids to return
+/// - `offset`: The number of leading sorted offset ids to skip
+///
+/// # Inputs
+/// - `logs`: The latest log of the collection
+/// - `blockfile_provider`: The blockfile provider
+/// - `metadata_segment`: The metadata segment information
+/// - `record_segment`: The record segment information
+/// - `filter_output`: The offset ids allowed by a prior `FilterOperator` run
+///
+/// # Outputs
+/// - `offset_ids`: Up to `limit` offset ids sorted by `key`, after skipping the first `offset`.
+///   Offsets missing `key` entirely sort last, in ascending offset order
+#[derive(Clone, Debug)]
+pub struct OrderByOperator {
+    pub key: String,
+    pub direction: SortDirection,
+    pub limit: u64,
+    pub offset: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct OrderByInput {
+    pub logs: Chunk<LogRecord>,
+    pub blockfile_provider: BlockfileProvider,
+    pub metadata_segment: Segment,
+    pub record_segment: Segment,
+    pub filter_output: FilterOutput,
+}
+
+#[derive(Clone, Debug)]
+pub struct OrderByOutput {
+    pub offset_ids: Vec<u32>,
+}
+
+#[derive(Error, Debug)]
+pub enum OrderByError {
+    #[error("Error computing order by: {0}")]
+    Filter(#[from] FilterError),
+}
+
+impl ChromaError for OrderByError {
+    fn code(&self) -> ErrorCodes {
+        match self {
+            OrderByError::Filter(e) => e.code(),
+        }
+    }
+}
+
+/// Resolves `allowed` to a concrete, enumerable set of offset ids against `universe` (the full
+/// set of ids this side could ever hold). Returns `None` if `allowed` excludes offsets and
+/// `universe` isn't known, since there would be no way to enumerate what's excluded.
+fn resolve_concrete(
+    allowed: &SignedRoaringBitmap,
+    universe: Option<&RoaringBitmap>,
+) -> Option<RoaringBitmap> {
+    match allowed {
+        SignedRoaringBitmap::Include(ids) => Some(ids.clone()),
+        SignedRoaringBitmap::Exclude(excluded) => universe.map(|u| u - excluded),
+    }
+}
+
+#[async_trait]
+impl Operator<OrderByInput, OrderByOutput> for OrderByOperator {
+    type Error = OrderByError;
+
+    async fn run(&self, input: &OrderByInput) -> Result<OrderByOutput, OrderByError> {
+        tracing::debug!("[{}]: {:?}", self.get_name(), input);
+
+        let record_segment_reader = match RecordSegmentReader::from_segment(
+            &input.record_segment,
+            &input.blockfile_provider,
+        )
+        .await
+        {
+            Ok(reader) => Ok(Some(reader)),
+            Err(e) if matches!(*e, RecordSegmentReaderCreationError::UninitializedSegment) => {
+                Ok(None)
+            }
+            Err(e) => Err(FilterError::RecordReader(*e)),
+        }?;
+        let cloned_record_segment_reader = record_segment_reader.clone();
+        let materialized_logs =
+            materialize_logs(&cloned_record_segment_reader, input.logs.clone(), None)
+                .instrument(tracing::trace_span!(parent: Span::current(), "Materialize logs"))
+                .await
+                .map_err(FilterError::LogMaterializer)?;
+        let metadata_log_reader =
+            MetadataLogReader::create(&materialized_logs, &record_segment_reader)
+                .await
+                .map_err(FilterError::LogMaterializer)?;
+        let log_metadata_provider = MetadataProvider::Log(&metadata_log_reader);
+
+        let metadata_segment_reader =
+            MetadataSegmentReader::from_segment(&input.metadata_segment, &input.blockfile_provider)
+                .await
+                .map_err(FilterError::MetadataReader)?;
+        let compact_metadata_provider =
+            MetadataProvider::CompactData(&metadata_segment_reader, &record_segment_reader);
+
+        // The number of sorted ids we need before we can stop looking at further values.
+        let target = self.offset.saturating_add(self.limit) as usize;
+
+        // Each side's distinct values for `key` are walked in key order and restricted to that
+        // side's allowed offsets; the two already-sorted streams are merged by simply pooling all
+        // groups and re-sorting by value, one group per distinct value.
+        let mut keyed_groups: Vec<(MetadataValue, RoaringBitmap)> = Vec::new();
+        let mut ids_with_key = RoaringBitmap::new();
+
+        for (provider, allowed) in [
+            (&log_metadata_provider, &input.filter_output.log_offset_ids),
+            (
+                &compact_metadata_provider,
+                &input.filter_output.compact_offset_ids,
+            ),
+        ] {
+            for (value, ids) in provider.facet_values(&self.key).await? {
+                // Intersecting a concrete (`Include`) set with anything always yields a concrete,
+                // finite result, regardless of whether `allowed` is an `Include` or `Exclude` set.
+                let matching = match SignedRoaringBitmap::Include(ids) & allowed.clone() {
+                    SignedRoaringBitmap::Include(ids) => ids,
+                    SignedRoaringBitmap::Exclude(_) => {
+                        unreachable!("intersecting with a concrete set is always concrete")
+                    }
+                };
+                if matching.is_empty() {
+                    continue;
+                }
+                ids_with_key |= &matching;
+                keyed_groups.push((value, matching));
+            }
+        }
+
+        keyed_groups.sort_by(|(a, _), (b, _)| a.cmp(b));
+        if self.direction == SortDirection::Descending {
+            keyed_groups.reverse();
+        }
+
+        let mut offset_ids = Vec::new();
+        'keyed: for (_, ids) in keyed_groups {
+            for id in ids {
+                offset_ids.push(id);
+                if offset_ids.len() >= target {
+                    break 'keyed;
+                }
+            }
+        }
+
+        // Records missing `key` entirely sort last, regardless of direction. Only resolvable on
+        // sides where the allowed set is either already concrete or its full universe is known.
+        if offset_ids.len() < target {
+            let log_universe = metadata_log_reader.all_offset_ids();
+            // The compacted side's universe is every offset id the record segment currently
+            // holds, mirroring how `MetadataProvider::CompactData`'s exact-match path falls back
+            // to `rec_reader.get_offset_stream(..)` for the same "all compacted ids" need. An
+            // uninitialized segment simply has no compacted ids yet.
+            let compact_universe = match record_segment_reader.as_ref() {
+                Some(reader) => reader
+                    .get_offset_stream(..)
+                    .try_collect::<RoaringBitmap>()
+                    .await
+                    .map_err(FilterError::Record)?,
+                None => RoaringBitmap::new(),
+            };
+            let mut missing = RoaringBitmap::new();
+            if let Some(ids) =
+                resolve_concrete(&input.filter_output.log_offset_ids, Some(&log_universe))
+            {
+                missing |= ids - &ids_with_key;
+            }
+            if let Some(ids) = resolve_concrete(
+                &input.filter_output.compact_offset_ids,
+                Some(&compact_universe),
+            ) {
+                missing |= ids - &ids_with_key;
+            }
+            for id in missing {
+                offset_ids.push(id);
+                if offset_ids.len() >= target {
+                    break;
+                }
+            }
+        }
+
+        let offset_ids = offset_ids
+            .into_iter()
+            .skip(self.offset as usize)
+            .take(self.limit as usize)
+            .collect();
+
+        Ok(OrderByOutput { offset_ids })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chroma_log::test::{add_delete_generator, int_as_id, LoadFromGenerator, LogGenerator};
+    use chroma_segment::test::TestDistributedSegment;
+    use chroma_system::Operator;
+
+    use crate::execution::operators::filter::{FilterInput, FilterOperator, FilterStrategy};
+
+    use super::{OrderByInput, OrderByOperator, SortDirection};
+
+    /// Uses the same fixture as `FilterOperator`'s tests: 120 log records, the first 60 of which
+    /// are compacted.
+    /// - Log: Delete [11..=20], add [51..=100]
+    /// - Compacted: Delete [1..=10], add [11..=50]
+    /// So the visible universe is [21..=50] (compacted) and [51..=100] (log), 80 records total,
+    /// each holding an "id" metadata key equal to its own offset id.
+    async fn setup_order_by_input(query_ids: Option<Vec<String>>) -> OrderByInput {
+        let mut test_segment = TestDistributedSegment::default();
+        test_segment
+            .populate_with_generator(60, add_delete_generator)
+            .await;
+
+        let filter_input = FilterInput {
+            logs: add_delete_generator.generate_chunk(61..=120),
+            blockfile_provider: test_segment.blockfile_provider,
+            metadata_segment: test_segment.metadata_segment,
+            record_segment: test_segment.record_segment,
+        };
+
+        let filter_output = FilterOperator {
+            query_ids,
+            where_clause: None,
+            strategy: FilterStrategy::Auto,
+        }
+        .run(&filter_input)
+        .await
+        .expect("FilterOperator should not fail");
+
+        OrderByInput {
+            logs: filter_input.logs,
+            blockfile_provider: filter_input.blockfile_provider,
+            metadata_segment: filter_input.metadata_segment,
+            record_segment: filter_input.record_segment,
+            filter_output,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_order_by_ascending_with_limit_and_offset() {
+        let input = setup_order_by_input(None).await;
+
+        let output = OrderByOperator {
+            key: "id".to_string(),
+            direction: SortDirection::Ascending,
+            limit: 5,
+            offset: 2,
+        }
+        .run(&input)
+        .await
+        .expect("OrderByOperator should not fail");
+
+        // The full ascending order is [21..=50] ++ [51..=100], so skipping 2 and taking 5 lands on
+        // offsets 23..=27.
+        assert_eq!(output.offset_ids, vec![23, 24, 25, 26, 27]);
+    }
+
+    #[tokio::test]
+    async fn test_order_by_descending() {
+        let input = setup_order_by_input(None).await;
+
+        let output = OrderByOperator {
+            key: "id".to_string(),
+            direction: SortDirection::Descending,
+            limit: 3,
+            offset: 0,
+        }
+        .run(&input)
+        .await
+        .expect("OrderByOperator should not fail");
+
+        assert_eq!(output.offset_ids, vec![100, 99, 98]);
+    }
+
+    #[tokio::test]
+    async fn test_order_by_missing_key_sorts_last() {
+        // Covering the whole visible universe with `query_ids` makes both sides' allowed sets
+        // concrete, so missing-key detection (which needs an enumerable universe) is exercised on
+        // the compacted side too, not just the always-bounded log side.
+        let input = setup_order_by_input(Some((0..120).map(int_as_id).collect())).await;
+
+        let output = OrderByOperator {
+            key: "no_such_key".to_string(),
+            direction: SortDirection::Ascending,
+            limit: 80,
+            offset: 0,
+        }
+        .run(&input)
+        .await
+        .expect("OrderByOperator should not fail");
+
+        let mut expected: Vec<u32> = (21..=50).chain(51..=100).collect();
+        expected.sort_unstable();
+        assert_eq!(output.offset_ids, expected);
+    }
+
+    #[tokio::test]
+    async fn test_order_by_missing_key_sorts_last_with_excluded_compact_offset_ids() {
+        // With no `query_ids`, `compact_offset_ids` is a `SignedRoaringBitmap::Exclude` set (the
+        // ordinary "order the whole collection" case), unlike the test above which only covers the
+        // `Include` case. Missing-key detection on the compacted side must still work here.
+        let input = setup_order_by_input(None).await;
+
+        let output = OrderByOperator {
+            key: "no_such_key".to_string(),
+            direction: SortDirection::Ascending,
+            limit: 80,
+            offset: 0,
+        }
+        .run(&input)
+        .await
+        .expect("OrderByOperator should not fail");
+
+        let mut expected: Vec<u32> = (21..=50).chain(51..=100).collect();
+        expected.sort_unstable();
+        assert_eq!(output.offset_ids, expected);
+    }
+}